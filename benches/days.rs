@@ -0,0 +1,48 @@
+use aoc_2020::generate;
+use aoc_2020::input::{self, InputSource};
+use aoc_2020::solution::Solution;
+use aoc_2020::{day1, day2, day3, day4, day5, day6, day7, day8};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// benchmarks parsing and both parts of day `S`, on its real input (when it's there)
+/// and on a generated input of `size` records
+fn bench_day<S: Solution>(c: &mut Criterion, size: usize) {
+    let name = format!("day{}", S::DAY);
+    let mut inputs = Vec::new();
+
+    match InputSource::Dir(input::inputs_dir(None)).read(&name) {
+        Ok(real) => inputs.push(("real", real)),
+        Err(err) => eprintln!("skipping {} on its real input: {}", name, err),
+    }
+
+    let generated = generate::generate(S::DAY, size, 0).unwrap();
+    inputs.push(("generated", generated.input));
+
+    for (kind, input) in inputs {
+        let mut group = c.benchmark_group(format!("{}/{}", name, kind));
+        group.sample_size(20);
+
+        let parsed = S::parse_input(&input).unwrap();
+
+        group.bench_function("parse", |b| b.iter(|| S::parse_input(black_box(&input))));
+        group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+        group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+        group.finish();
+    }
+}
+
+fn days(c: &mut Criterion) {
+    let size = generate::DEFAULT_SIZE;
+
+    bench_day::<day1::Day1>(c, size);
+    bench_day::<day2::Day2>(c, size);
+    bench_day::<day3::Day3>(c, size);
+    bench_day::<day4::Day4>(c, size);
+    bench_day::<day5::Day5>(c, size);
+    bench_day::<day6::Day6>(c, size);
+    bench_day::<day7::Day7>(c, size);
+    bench_day::<day8::Day8>(c, size);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,157 @@
+use serde_json::{json, Value};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A part's answer, in whichever form the puzzle asks for it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    /// an integer too big for an `i64`
+    BigInteger(i128),
+    String(String),
+    /// several lines of text, like letters drawn out in ascii art
+    Text(String),
+    /// why a part couldn't come up with an answer, like a product too big to represent
+    Error(String),
+    /// a part with nothing to report for this input, like an expense report without a matching pair
+    None,
+}
+
+impl Answer {
+    /// whether two answers are the same, however they were written down; `42` matches `"42"`,
+    /// and text matches regardless of trailing whitespace on its lines
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Error(_), _) | (_, Answer::Error(_)) => false,
+            (Answer::None, _) | (_, Answer::None) => false,
+            _ => self.normalized() == other.normalized(),
+        }
+    }
+
+    fn normalized(&self) -> String {
+        match self {
+            Answer::Integer(_) | Answer::BigInteger(_) | Answer::Error(_) | Answer::None => {
+                self.to_string()
+            }
+            Answer::String(answer) => answer.trim().to_string(),
+            Answer::Text(text) => text
+                .trim_matches('\n')
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// integers that fit in json's numbers are written as numbers, no answer (or an error
+    /// in place of one) as `null`, and everything else as strings
+    pub fn to_json(&self) -> Value {
+        match self {
+            Answer::Integer(answer) => json!(answer),
+            Answer::None | Answer::Error(_) => Value::Null,
+            _ => json!(self.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(answer) => write!(f, "{}", answer),
+            Answer::BigInteger(answer) => write!(f, "{}", answer),
+            Answer::String(answer) | Answer::Text(answer) => write!(f, "{}", answer),
+            Answer::Error(reason) => write!(f, "error: {}", reason),
+            Answer::None => write!(f, "none"),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(answer: $int) -> Self {
+                    match i64::try_from(answer) {
+                        Ok(answer) => Answer::Integer(answer),
+                        Err(_) => Answer::BigInteger(answer as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+/// answers that don't fit in an `i128` either fall back to being written out as a string
+impl From<u128> for Answer {
+    fn from(answer: u128) -> Self {
+        match i128::try_from(answer) {
+            Ok(answer) => answer.into(),
+            Err(_) => Answer::String(answer.to_string()),
+        }
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        match answer {
+            Some(answer) => answer.into(),
+            None => Answer::None,
+        }
+    }
+}
+
+impl<T: Into<Answer>, E: fmt::Display> From<Result<T, E>> for Answer {
+    fn from(answer: Result<T, E>) -> Self {
+        match answer {
+            Ok(answer) => answer.into(),
+            Err(err) => Answer::Error(err.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Self {
+        match answer.trim_matches('\n').contains('\n') {
+            true => Answer::Text(answer),
+            false => Answer::String(answer),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Self {
+        answer.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(42u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-7i32), Answer::Integer(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(Answer::from("abc"), Answer::String("abc".to_string()));
+        assert_eq!(
+            Answer::from("#.\n.#\n"),
+            Answer::Text("#.\n.#\n".to_string())
+        );
+        assert_eq!(Answer::from(None::<u32>), Answer::None);
+        assert_eq!(Answer::None.to_json(), Value::Null);
+        assert_eq!(Answer::Error("too big".to_string()).to_json(), Value::Null);
+    }
+
+    #[test]
+    fn matches() {
+        assert!(Answer::from(241861950u32).matches(&Answer::from("241861950")));
+        assert!(Answer::from(1u64 << 63).matches(&Answer::from("9223372036854775808")));
+        assert!(!Answer::from(1).matches(&Answer::from(2)));
+        assert!(!Answer::Error("1".to_string()).matches(&Answer::from("error: 1")));
+        assert!(Answer::from("#. \n.#\n").matches(&Answer::from("\n#.\n.#")));
+        assert!(!Answer::None.matches(&Answer::from("none")));
+    }
+}
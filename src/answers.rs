@@ -0,0 +1,156 @@
+use crate::answer::Answer;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use toml::Value;
+
+/// the answers file's name within the inputs directory
+pub const FILE_NAME: &str = "answers.toml";
+
+/// The accepted answers for each day's real input, keyed by day and part.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u32, u32), Answer>,
+}
+
+/// How a computed answer compares with the recorded one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("couldn't read answers from {}: {}", path.display(), err))?;
+
+        contents
+            .parse()
+            .map_err(|err| format!("invalid answers in {}: {}", path.display(), err))
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: Answer) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected.matches(answer) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// parses tables like `[day1]` holding `part1`/`part2` keys; answers can be
+/// written as integers or strings, and multi-line strings are read as text answers
+impl std::str::FromStr for Answers {
+    type Err = String;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for (day_name, parts) in parse_tables(contents)? {
+            let day = parse_key(&day_name, "day")?;
+
+            for (part, answer) in parse_parts(&day_name, &parts)? {
+                answers.insert(day, part, answer);
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// the top-level tables of a toml document, by name
+pub fn parse_tables(contents: &str) -> Result<toml::value::Table, String> {
+    match contents.parse::<Value>().map_err(|err| err.to_string())? {
+        Value::Table(table) => Ok(table),
+        _ => unreachable!("a toml document is always a table"),
+    }
+}
+
+/// the answers in a table like `[day1]`, holding `part1`/`part2` keys
+pub fn parse_parts(name: &str, parts: &Value) -> Result<Vec<(u32, Answer)>, String> {
+    let parts = parts
+        .as_table()
+        .ok_or_else(|| format!("expected [{}] to be a table", name))?;
+
+    parts
+        .iter()
+        .map(|(part_name, answer)| {
+            let part = parse_key(part_name, "part")?;
+            let answer = match answer {
+                Value::String(answer) => Answer::from(answer.as_str()),
+                Value::Integer(answer) => Answer::Integer(*answer),
+                _ => {
+                    return Err(format!(
+                        "{}.{} should be a string or integer",
+                        name, part_name
+                    ))
+                }
+            };
+
+            Ok((part, answer))
+        })
+        .collect()
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u32, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| format!("expected a key like `{}1`, found `{}`", prefix, key))
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_ANSWERS: &str = "\
+[day1]
+part1 = 514579
+part2 = \"241861950\"
+
+[day2]
+part1 = 2
+";
+
+    #[test]
+    fn check() {
+        let answers: Answers = EXAMPLE_ANSWERS.parse().unwrap();
+        assert_eq!(answers.check(1, 1, &514579.into()), Verdict::Pass);
+        assert_eq!(answers.check(1, 2, &241861950.into()), Verdict::Pass);
+        assert_eq!(
+            answers.check(2, 1, &3.into()),
+            Verdict::Fail {
+                expected: Answer::Integer(2)
+            }
+        );
+        assert_eq!(answers.check(2, 2, &1.into()), Verdict::Unknown);
+    }
+
+    #[test]
+    fn invalid_answers() {
+        assert!("[dayone]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[day1]\nfirst = 1".parse::<Answers>().is_err());
+        assert!("[day1]\npart1 = 1.5".parse::<Answers>().is_err());
+    }
+}
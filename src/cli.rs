@@ -0,0 +1,316 @@
+use crate::answers;
+use crate::input::{self, InputSource};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage: aoc-2020 [DAYS...] [--part N] [--bench N] [--check] [--answers PATH]
+                [--format text|json] [--input PATH|-] [--input-text TEXT]
+                [--inputs-dir DIR] [--parallel] [--jobs N] [--watch] [--example]
+                [--no-check] [--no-timings] [--timings] [--config PATH]
+       aoc-2020 new-day N
+       aoc-2020 generate N [--size N] [--seed N]
+       aoc-2020 repl N [--input PATH] [--inputs-dir DIR]
+
+    new-day N       create and register a module, input file and test stub for day N
+    generate N      print a random input for day N, and any answers planted in it to stderr
+    --size          how many records (lines, passports, groups, ...) to generate
+    --seed          generate the same input every time (default: a random seed)
+    repl N          load day N's input and explore it interactively (`help` lists commands)
+
+    DAYS            days to run, e.g. `7`, `day7`, `1..5` or `1..=5` (default: all)
+    -p, --part      only run the given part (1 or 2)
+    -b, --bench     repeat each phase N times and report min/median/max timings
+    -c, --check     compare answers with the recorded ones (PASS/FAIL/UNKNOWN)
+    --no-check      don't compare answers, even if the config file asks to
+    --answers       where the recorded answers live (default: answers.toml in the inputs dir)
+    -f, --format    print results as `text` (default) or `json`, one object per line
+    -i, --input     read the (single) selected day's input from a file, or stdin with `-`
+    --input-text    use the given text as the (single) selected day's input
+    --inputs-dir    where to find `dayN.txt` inputs (default: $AOC_INPUTS_DIR, or `inputs`)
+    --parallel      run the selected days concurrently, one thread per core
+    -j, --jobs      run the selected days concurrently on N threads
+    -w, --watch     re-run the (single) selected day's tests and input whenever they change
+    -e, --example   run against the puzzle examples in `examples/` instead of the real input
+    --no-timings    don't print the timing table after the answers
+    --timings       print the timing table, even if the config file turns it off
+    --config        read default options from the given file (default: `aoc.toml`, if present)
+    -h, --help      print this message";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    /// the requested days, in the order they were given; empty means every day
+    pub days: Vec<u32>,
+    pub part: Option<u32>,
+    /// how many times to repeat each phase when benchmarking
+    pub bench: Option<usize>,
+    pub check: bool,
+    /// the answers file to check against, instead of the default one
+    pub answers: Option<String>,
+    pub format: Format,
+    /// where to read input from, instead of the inputs directory
+    pub input: Option<InputSource>,
+    pub inputs_dir: Option<String>,
+    /// how many threads to run days on; `None` runs them one after another
+    pub jobs: Option<usize>,
+    pub watch: bool,
+    pub example: bool,
+    pub no_timings: bool,
+    /// the config file given on the command line
+    pub config: Option<String>,
+    /// how big a generated input should be
+    pub size: Option<usize>,
+    pub seed: Option<u64>,
+    pub help: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// solve the selected days
+    #[default]
+    Run,
+    /// scaffold the given day
+    NewDay(u32),
+    /// print a random input for the given day
+    Generate(u32),
+    /// explore the given day's input interactively
+    Repl(u32),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Options {
+    pub fn input_source(&self) -> InputSource {
+        match &self.input {
+            Some(input) => input.clone(),
+            None => InputSource::Dir(input::inputs_dir(self.inputs_dir.as_deref())),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.answers {
+            Some(path) => path.into(),
+            None => input::inputs_dir(self.inputs_dir.as_deref()).join(answers::FILE_NAME),
+        }
+    }
+
+    pub fn parts(&self) -> Vec<u32> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    parse_args_with(Options::default(), args)
+}
+
+/// parses `args` on top of `defaults`, such as ones read from a config file;
+/// any days given in `args` replace the default ones rather than adding to them
+pub fn parse_args_with<I: IntoIterator<Item = String>>(
+    defaults: Options,
+    args: I,
+) -> Result<Options, String> {
+    let mut options = defaults;
+    let mut days = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // options can be given as either `--name value` or `--name=value`
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+
+        match name {
+            "-h" | "--help" => options.help = true,
+            "new-day" => options.command = Command::NewDay(parse_day(&value()?)?),
+            "generate" => options.command = Command::Generate(parse_day(&value()?)?),
+            "repl" => options.command = Command::Repl(parse_day(&value()?)?),
+            "--size" => options.size = Some(parse_count(&value()?)?),
+            "--seed" => {
+                let seed = value()?;
+                let seed = seed
+                    .parse()
+                    .map_err(|_| format!("invalid seed: {}", seed))?;
+                options.seed = Some(seed);
+            }
+            "-p" | "--part" => options.part = Some(parse_part(&value()?)?),
+            "-b" | "--bench" => options.bench = Some(parse_count(&value()?)?),
+            "-c" | "--check" => options.check = true,
+            "--no-check" => options.check = false,
+            "-f" | "--format" => options.format = parse_format(&value()?)?,
+            "-i" | "--input" => options.input = Some(InputSource::from_arg(&value()?)),
+            "--input-text" => options.input = Some(InputSource::Text(value()?)),
+            "--inputs-dir" => options.inputs_dir = Some(value()?),
+            "--parallel" => {
+                let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+                options.jobs = Some(cores);
+            }
+            "-j" | "--jobs" => options.jobs = Some(parse_count(&value()?)?),
+            "-w" | "--watch" => options.watch = true,
+            "-e" | "--example" => options.example = true,
+            "--no-timings" => options.no_timings = true,
+            "--timings" => options.no_timings = false,
+            "--config" => options.config = Some(value()?),
+            "--answers" => {
+                options.answers = Some(value()?);
+                options.check = true;
+            }
+            _ if name.starts_with('-') => return Err(format!("unknown option: {}", arg)),
+            _ => {
+                for day in parse_days(&arg)? {
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                }
+            }
+        }
+    }
+
+    if !days.is_empty() {
+        options.days = days;
+    }
+
+    Ok(options)
+}
+
+pub(crate) fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("invalid format: {} (expected text or json)", value)),
+    }
+}
+
+pub(crate) fn parse_count(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "invalid count: {} (expected a positive number)",
+            value
+        )),
+    }
+}
+
+pub(crate) fn parse_part(value: &str) -> Result<u32, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part: {} (expected 1 or 2)", value)),
+    }
+}
+
+/// parses a single day (`7`, `day7`) or a range of days (`1..5`, `day1..=day5`)
+pub(crate) fn parse_days(spec: &str) -> Result<Vec<u32>, String> {
+    let days: Vec<u32> = if let Some((start, end)) = spec.split_once("..=") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        (start..=end).collect()
+    } else if let Some((start, end)) = spec.split_once("..") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        (start..end).collect()
+    } else {
+        return parse_day(spec).map(|day| vec![day]);
+    };
+
+    // no days at all would otherwise mean running every day
+    match days.is_empty() {
+        true => Err(format!("empty day range: {}", spec)),
+        false => Ok(days),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    day.strip_prefix("day")
+        .unwrap_or(day)
+        .parse()
+        .map_err(|_| format!("invalid day: {}", day))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn days_and_part() {
+        let options = parse(&["day7", "3", "--part", "2"]).unwrap();
+        assert_eq!(options.days, vec![7, 3]);
+        assert_eq!(options.part, Some(2));
+
+        let options = parse(&["1..3", "day2..=day4", "--bench=10"]).unwrap();
+        assert_eq!(options.days, vec![1, 2, 3, 4]);
+        assert_eq!(options.part, None);
+        assert_eq!(options.bench, Some(10));
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(options.jobs, None);
+
+        let options = parse(&["-f", "json", "8", "--input", "-"]).unwrap();
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.input, Some(InputSource::Stdin));
+
+        let options = parse(&["new-day", "9"]).unwrap();
+        assert_eq!(options.command, Command::NewDay(9));
+
+        let options = parse(&["generate", "day3", "--size", "10", "--seed=7"]).unwrap();
+        assert_eq!(options.command, Command::Generate(3));
+        assert_eq!((options.size, options.seed), (Some(10), Some(7)));
+
+        let options = parse(&["repl", "8", "--input", "mine.txt"]).unwrap();
+        assert_eq!(options.command, Command::Repl(8));
+        assert_eq!(options.input, Some(InputSource::File("mine.txt".into())));
+
+        let options = parse(&["--jobs", "4"]).unwrap();
+        assert_eq!(options.jobs, Some(4));
+
+        let defaults = Options {
+            days: vec![1, 2],
+            check: true,
+            ..Options::default()
+        };
+        let options = parse_args_with(defaults, vec!["5".to_string()]).unwrap();
+        assert_eq!(options.days, vec![5]);
+        assert!(options.check);
+
+        // flags can switch off what the config file switched on, and the other way around
+        let defaults = Options {
+            check: true,
+            no_timings: true,
+            ..Options::default()
+        };
+        let args = vec!["--no-check".to_string(), "--timings".to_string()];
+        let options = parse_args_with(defaults, args).unwrap();
+        assert!(!options.check && !options.no_timings);
+
+        let options = parse(&["--inputs-dir", "elsewhere"]).unwrap();
+        assert_eq!(options.input_source(), InputSource::Dir("elsewhere".into()));
+    }
+
+    #[test]
+    fn invalid_args() {
+        assert!(parse(&["seven"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["--bench", "0"]).is_err());
+        assert!(parse(&["--bench"]).is_err());
+
+        assert_eq!(parse(&["5..1"]).unwrap_err(), "empty day range: 5..1");
+        assert!(parse(&["3..3"]).is_err());
+        assert!(parse(&["day4..=day2"]).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::answers;
+use crate::cli::{self, Options};
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// the config file's name, looked for in the current directory
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// the config file to use: the one given with `--config`, or `aoc.toml` if there is one
+pub fn path(explicit: Option<&str>) -> Option<PathBuf> {
+    match explicit {
+        Some(path) => Some(path.into()),
+        None => Some(PathBuf::from(FILE_NAME)).filter(|path| path.exists()),
+    }
+}
+
+/// reads the default options from a config file; anything given on the command line
+/// still takes precedence over them
+pub fn load(path: &Path) -> Result<Options, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read config from {}: {}", path.display(), err))?;
+
+    parse(&contents).map_err(|err| format!("invalid config in {}: {}", path.display(), err))
+}
+
+/// parses top-level settings like `days = "1..=8"`, `format = "json"` or `check = true`
+pub fn parse(contents: &str) -> Result<Options, String> {
+    let mut options = Options::default();
+
+    for (key, value) in answers::parse_tables(contents)? {
+        match key.as_str() {
+            "days" => options.days = parse_days(&value)?,
+            "part" => options.part = Some(cli::parse_part(&scalar(&key, &value)?)?),
+            "format" => options.format = cli::parse_format(&string(&key, &value)?)?,
+            "inputs_dir" => options.inputs_dir = Some(string(&key, &value)?),
+            "answers" => options.answers = Some(string(&key, &value)?),
+            "check" => options.check = boolean(&key, &value)?,
+            "timings" => options.no_timings = !boolean(&key, &value)?,
+            "jobs" => options.jobs = Some(cli::parse_count(&scalar(&key, &value)?)?),
+            _ => return Err(format!("unknown setting `{}`", key)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// days can be a single spec like `"1..=8"`, or a list of days and ranges
+fn parse_days(value: &Value) -> Result<Vec<u32>, String> {
+    let specs = match value {
+        Value::Array(specs) => specs
+            .iter()
+            .map(|spec| scalar("days", spec))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => vec![scalar("days", value)?],
+    };
+
+    let mut days = Vec::new();
+    for spec in specs {
+        for day in cli::parse_days(&spec)? {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    Ok(days)
+}
+
+fn string(key: &str, value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("`{}` should be a string", key))
+}
+
+/// a string or an integer, as the text it would be given as on the command line
+fn scalar(key: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Integer(value) => Ok(value.to_string()),
+        _ => Err(format!("`{}` should be a string or integer", key)),
+    }
+}
+
+fn boolean(key: &str, value: &Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("`{}` should be true or false", key))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cli::Format;
+
+    #[test]
+    fn settings() {
+        let options = parse(
+            "days = [1, \"day3..=5\"]\nformat = \"json\"\ninputs_dir = \"ci\"\ncheck = true\ntimings = false\n",
+        )
+        .unwrap();
+
+        assert_eq!(options.days, vec![1, 3, 4, 5]);
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.inputs_dir.as_deref(), Some("ci"));
+        assert!(options.check && options.no_timings);
+
+        let options = parse("days = \"1..3\"\npart = 2\njobs = 4").unwrap();
+        assert_eq!(options.days, vec![1, 2]);
+        assert_eq!((options.part, options.jobs), (Some(2), Some(4)));
+    }
+
+    #[test]
+    fn invalid_settings() {
+        assert!(parse("colour = true").is_err());
+        assert!(parse("check = \"yes\"").is_err());
+        assert!(parse("format = \"xml\"").is_err());
+        assert!(parse("days = [\"seven\"]").is_err());
+    }
+}
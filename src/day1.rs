@@ -1,56 +1,743 @@
+use crate::error::{DayError, LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
 
-pub fn parse_input(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .map(|line| line.parse::<u32>().expect("couldn't parse line into u32"))
-        .collect()
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<Expense>;
+    type Output1 = Result<Option<i128>, ProductOverflow>;
+    type Output2 = Result<Option<i128>, ProductOverflow>;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Expense>, ParseError> {
+    util::parse_lines(Day1::DAY, input, parse_entry)
+}
+
+fn parse_entry(line: &str) -> Result<Expense, LineError> {
+    line.parse()
+        .map_err(|_| LineError::new(1, "expected a whole number"))
+}
+
+/// reads a report a line at a time into `watcher`, stopping as soon as two entries sum to
+/// its target; nothing after the second entry is read, so the rest of a huge report never
+/// has to be, and the same watcher can pick up where it left off once more lines arrive
+///
+/// a last line without its newline may still be being written, so it's kept in `watcher`
+/// to be joined with the next read; `PairWatcher::finish` takes it once the report is done
+pub fn find_pair_streaming<R: BufRead>(
+    mut reader: R,
+    watcher: &mut PairWatcher,
+) -> Result<Option<LinePair>, DayError> {
+    loop {
+        reader.read_line(&mut watcher.pending).map_err(|err| {
+            DayError::Input(format!("couldn't read line {}: {}", watcher.lines + 1, err))
+        })?;
+
+        // `read_line` only stops short of a newline at the end of what's there so far
+        if !watcher.pending.ends_with('\n') {
+            return Ok(None);
+        }
+
+        let line = std::mem::take(&mut watcher.pending);
+        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        if let Some(pair) = watcher.push_line(line)? {
+            return Ok(Some(pair));
+        }
+    }
+}
+
+/// Two entries that sum to the target, along with the lines they were on.
+#[derive(Debug, PartialEq)]
+pub struct LinePair {
+    /// 1-based, earlier line first
+    pub lines: (usize, usize),
+    pub entries: (Expense, Expense),
+}
+
+impl LinePair {
+    /// the product of two entries always fits in an `i128`
+    pub fn product(&self) -> i128 {
+        self.entries.0 as i128 * self.entries.1 as i128
+    }
 }
 
-pub fn compute_part1(values: Vec<u32>) -> u32 {
-    let (a, b) = values
+/// Takes a report's entries one at a time, spotting a pair that sums to the target as soon
+/// as its second entry shows up; keep one around to follow a report that's still growing.
+pub struct PairWatcher {
+    target: i128,
+    /// every entry so far, and the first line it was on
+    seen: HashMap<i128, usize>,
+    lines: usize,
+    /// the start of a line that `find_pair_streaming` hasn't seen the end of yet
+    pending: String,
+}
+
+impl PairWatcher {
+    pub fn new(target: Expense) -> Self {
+        PairWatcher::wide(target as i128)
+    }
+
+    fn wide(target: i128) -> Self {
+        PairWatcher {
+            target,
+            seen: HashMap::new(),
+            lines: 0,
+            pending: String::new(),
+        }
+    }
+
+    /// takes the entry on the report's next line, returning the pair it completes, if any
+    pub fn push(&mut self, entry: Expense) -> Option<LinePair> {
+        self.lines += 1;
+        let wanted = self.target - entry as i128;
+
+        if let Some(&line) = self.seen.get(&wanted) {
+            return Some(LinePair {
+                lines: (line, self.lines),
+                entries: (wanted as Expense, entry),
+            });
+        }
+
+        self.seen.entry(entry as i128).or_insert(self.lines);
+        None
+    }
+
+    /// parses and takes the report's next line; a line that doesn't parse still counts,
+    /// so the lines after it keep their numbers
+    pub fn push_line(&mut self, line: &str) -> Result<Option<LinePair>, ParseError> {
+        match parse_entry(line) {
+            Ok(entry) => Ok(self.push(entry)),
+            Err(err) => {
+                self.lines += 1;
+                Err(err.at(Day1::DAY, self.lines - 1, line))
+            }
+        }
+    }
+
+    /// takes an unfinished last line left over from `find_pair_streaming` as complete,
+    /// once nothing more is going to be added to the report
+    pub fn finish(&mut self) -> Result<Option<LinePair>, ParseError> {
+        match self.pending.is_empty() {
+            true => Ok(None),
+            false => {
+                let line = std::mem::take(&mut self.pending);
+                self.push_line(&line)
+            }
+        }
+    }
+}
+
+/// an entry in the expense report; negative entries are refunds
+pub type Expense = i64;
+
+/// what the entries we're looking for should add up to
+pub const TARGET: Expense = 2020;
+
+/// the product of the first pair of entries that sum to 2020, if there is one
+pub fn compute_part1(values: &[Expense]) -> Result<Option<i128>, ProductOverflow> {
+    find_k_sum(values, 2, TARGET)
+        .map(|entries| product(&entries))
+        .transpose()
+}
+
+/// the product of three entries that sum to 2020, if there are any; they're searched for
+/// in sorted order, so of several matches this is one with the smallest entry that fits
+pub fn compute_part2(values: &[Expense]) -> Result<Option<i128>, ProductOverflow> {
+    find_k_sum(values, 3, TARGET)
+        .map(|entries| product(&entries))
+        .transpose()
+}
+
+/// multiplies `entries` together, as long as the result fits in an `i128`
+pub fn product(entries: &[Expense]) -> Result<i128, ProductOverflow> {
+    entries
         .iter()
-        .tuple_combinations()
-        .find(|&(a, b)| a + b == 2020)
-        .expect("didn't find any entries that sum to 2020");
+        .try_fold(1i128, |product, &entry| product.checked_mul(entry as i128))
+        .ok_or_else(|| ProductOverflow {
+            entries: entries.to_vec(),
+        })
+}
+
+/// The entries whose product is too big to represent.
+#[derive(Debug, PartialEq)]
+pub struct ProductOverflow {
+    pub entries: Vec<Expense>,
+}
+
+impl fmt::Display for ProductOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the product of {} doesn't fit in 128 bits",
+            self.entries.iter().join(" * ")
+        )
+    }
+}
+
+/// A set of entries that sum to the target, along with where they are in the report.
+#[derive(Debug, PartialEq)]
+pub struct Combination {
+    /// positions in the report, in ascending order
+    pub indices: Vec<usize>,
+    /// the entries at those positions, in the same order
+    pub entries: Vec<Expense>,
+}
+
+impl Combination {
+    pub fn product(&self) -> Result<i128, ProductOverflow> {
+        product(&self.entries)
+    }
+
+    /// the product however big it gets, since a subset can hold any number of entries
+    pub fn exact_product(&self) -> String {
+        exact_product(&self.entries)
+    }
+}
+
+/// multiplies `entries` together without any limit on the size of the result,
+/// and writes it out in decimal
+pub fn exact_product(entries: &[Expense]) -> String {
+    const BASE: u128 = 1_000_000_000_000_000_000;
+
+    // the magnitude in base 10^18, least significant digit first; a digit times any entry
+    // still fits in a `u128`
+    let mut digits = vec![1u128];
+    for &entry in entries {
+        let factor = entry.unsigned_abs() as u128;
+        let mut carry = 0;
+        for digit in digits.iter_mut() {
+            let value = *digit * factor + carry;
+            *digit = value % BASE;
+            carry = value / BASE;
+        }
+        while carry > 0 {
+            digits.push(carry % BASE);
+            carry /= BASE;
+        }
+        while digits.len() > 1 && digits.last() == Some(&0) {
+            digits.pop();
+        }
+    }
+
+    let negative = digits != [0] && entries.iter().filter(|&&entry| entry < 0).count() % 2 == 1;
+    let mut written = if negative {
+        "-".to_string()
+    } else {
+        String::new()
+    };
+    let mut digits = digits.iter().rev();
+    written += &digits.next().expect("there's always a digit").to_string();
+    for digit in digits {
+        written += &format!("{:018}", digit);
+    }
+
+    written
+}
+
+/// every distinct set of `k` entries (by position, so repeated values count separately)
+/// that sum to `target`
+pub fn combinations(values: &[Expense], k: usize, target: Expense) -> Combinations<'_> {
+    let order: Vec<usize> = (0..values.len())
+        .sorted_by_key(|&index| values[index])
+        .collect();
+    let sorted: Vec<i128> = order.iter().map(|&index| values[index] as i128).collect();
+    let largest = std::iter::once(0)
+        .chain(sorted.iter().rev().scan(0, |sum, &value| {
+            *sum += value;
+            Some(*sum)
+        }))
+        .collect();
+
+    Combinations {
+        values,
+        order,
+        sorted,
+        largest,
+        k,
+        target: target as i128,
+        stack: Vec::new(),
+        sum: 0,
+        next: 0,
+        done: false,
+    }
+}
+
+/// how many distinct sets of `k` entries sum to `target`; more than one means the report is
+/// ambiguous
+pub fn count_combinations(values: &[Expense], k: usize, target: Expense) -> usize {
+    combinations(values, k, target).count()
+}
+
+/// Searches through the entries in ascending order, depth first, giving up on a branch as
+/// soon as even its smallest possible entries would overshoot the target, and skipping
+/// straight past entries too small to reach it even alongside the largest ones.
+pub struct Combinations<'a> {
+    values: &'a [Expense],
+    /// the report's positions, sorted by their entries
+    order: Vec<usize>,
+    /// the entries in that same order
+    sorted: Vec<i128>,
+    /// the sum of the `n` largest entries, for every `n`
+    largest: Vec<i128>,
+    k: usize,
+    /// sums are kept wider than the entries, so adding them up can't overflow
+    target: i128,
+    /// positions in `order` picked so far
+    stack: Vec<usize>,
+    sum: i128,
+    /// the position in `order` to try next, at the current depth
+    next: usize,
+    done: bool,
+}
+
+impl Combinations<'_> {
+    fn value(&self, position: usize) -> i128 {
+        self.sorted[position]
+    }
+
+    /// undoes the latest pick, so the search carries on with the entry after it;
+    /// returns false once there's nothing left to undo
+    fn backtrack(&mut self) -> bool {
+        match self.stack.pop() {
+            Some(position) => {
+                self.sum -= self.value(position);
+                self.next = position + 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Iterator for Combinations<'_> {
+    type Item = Combination;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if self.stack.len() == self.k {
+                let found: Option<Vec<usize>> = match self.sum == self.target {
+                    true => Some(self.stack.iter().map(|&p| self.order[p]).sorted().collect()),
+                    false => None,
+                };
+                self.done = !self.backtrack();
+
+                if let Some(indices) = found {
+                    let entries = indices.iter().map(|&index| self.values[index]).collect();
+                    return Some(Combination { indices, entries });
+                }
+                continue;
+            }
+
+            // an entry can only make it with the help of the largest ones, so anything
+            // below what those leave over can't reach the target
+            let remaining = self.k - self.stack.len();
+            let helpers = self.largest[(remaining - 1).min(self.sorted.len())];
+            let needed = self.target - self.sum - helpers;
+            self.next += self.sorted[self.next..].partition_point(|&value| value < needed);
+
+            // the rest of the entries are at least as big as the next one, even when negative
+            let exhausted = self.next + remaining > self.order.len()
+                || self.sum + self.value(self.next) * remaining as i128 > self.target;
+
+            if exhausted {
+                self.done = !self.backtrack();
+                continue;
+            }
+
+            self.sum += self.value(self.next);
+            self.stack.push(self.next);
+            self.next += 1;
+        }
+
+        None
+    }
+}
+
+/// Whether `subset_sum` looks for the fewest entries it can, or settles for the first it finds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubsetMode {
+    Smallest,
+    Any,
+}
+
+/// finds entries (any number of them, each used at most once) that add up to `target`;
+/// a `target` of 0 is met by no entries at all
+///
+/// this works through the entries one at a time, keeping track of every sum reachable so far
+/// and the fewest entries it takes to get there, so it's quick when the entries only add up
+/// to a limited range of sums, but can be slow for a long report of widely spread entries
+pub fn subset_sum(values: &[Expense], target: Expense, mode: SubsetMode) -> Option<Combination> {
+    let target = target as i128;
+
+    // for every position, how low and how high the entries from there on could still take a sum
+    let mut reach = vec![(0i128, 0i128); values.len() + 1];
+    for (index, &value) in values.iter().enumerate().rev() {
+        let (low, high) = reach[index + 1];
+        reach[index] = match value < 0 {
+            true => (low + value as i128, high),
+            false => (low, high + value as i128),
+        };
+    }
+    let reachable = |sum: i128, position: usize| {
+        let (low, high) = reach[position];
+        sum + low <= target && target <= sum + high
+    };
+
+    // each picked entry links back to the one picked before it, so no path is ever changed
+    // once it's recorded; `sums` maps a sum to the fewest picks reaching it and the latest one
+    let mut picks: Vec<(usize, Option<usize>)> = Vec::new();
+    let mut sums: HashMap<i128, (usize, Option<usize>)> = HashMap::new();
+    if reachable(0, 0) {
+        sums.insert(0, (0, None));
+    }
+
+    for (index, &value) in values.iter().enumerate() {
+        if mode == SubsetMode::Any && sums.contains_key(&target) {
+            break;
+        }
+
+        let current: Vec<(i128, (usize, Option<usize>))> =
+            sums.iter().map(|(&sum, &state)| (sum, state)).collect();
+
+        for (sum, (count, last)) in current {
+            let next = sum + value as i128;
+            let better = sums
+                .get(&next)
+                .is_none_or(|&(existing, _)| count + 1 < existing);
+
+            if better && reachable(next, index + 1) {
+                picks.push((index, last));
+                sums.insert(next, (count + 1, Some(picks.len() - 1)));
+            }
+        }
+
+        sums.retain(|&sum, _| reachable(sum, index + 1));
+    }
+
+    let (_, mut last) = *sums.get(&target)?;
+    let mut indices = Vec::new();
+    while let Some(pick) = last {
+        let (index, previous) = picks[pick];
+        indices.push(index);
+        last = previous;
+    }
+    indices.reverse();
 
-    a * b
+    let entries = indices.iter().map(|&index| values[index]).collect();
+    Some(Combination { indices, entries })
 }
 
-pub fn compute_part2(values: Vec<u32>) -> u32 {
-    let (a, b, c) = values
+/// finds `k` entries (each used at most once) that add up to `target`, if there are any
+pub fn find_k_sum(values: &[Expense], k: usize, target: Expense) -> Option<Vec<Expense>> {
+    let target = target as i128;
+
+    match k {
+        0 => Some(Vec::new()).filter(|_| target == 0),
+        1 => values
+            .iter()
+            .find(|&&value| value as i128 == target)
+            .map(|&value| vec![value]),
+        2 => find_pair(values, target),
+        3 => find_triple(values, target),
+        _ => find_meet_in_the_middle(values, k, target),
+    }
+}
+
+/// one pass, remembering every entry seen so far
+fn find_pair(values: &[Expense], target: i128) -> Option<Vec<Expense>> {
+    let mut watcher = PairWatcher::wide(target);
+    values
         .iter()
-        .tuple_combinations()
-        .find(|&(a, b, c)| a + b + c == 2020)
-        .expect("didn't find any entries that sum to 2020");
+        .find_map(|&value| watcher.push(value))
+        .map(|pair| vec![pair.entries.0, pair.entries.1])
+}
+
+/// fixes the smallest entry, then closes in on the other two from both ends of the rest
+fn find_triple(values: &[Expense], target: i128) -> Option<Vec<Expense>> {
+    let sorted: Vec<i128> = values.iter().map(|&value| value as i128).sorted().collect();
+
+    for (first, &a) in sorted.iter().enumerate() {
+        let (mut low, mut high) = (first + 1, sorted.len().saturating_sub(1));
+        while low < high {
+            let sum = a + sorted[low] + sorted[high];
+            match sum.cmp(&target) {
+                Ordering::Equal => {
+                    let entries = [a, sorted[low], sorted[high]];
+                    return Some(entries.iter().map(|&entry| entry as Expense).collect());
+                }
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+            }
+        }
+    }
 
-    a * b * c
+    None
+}
+
+/// splits the `k` entries into two halves: every combination for the first half is indexed
+/// by its sum, then every combination for the second half looks up what it's missing
+fn find_meet_in_the_middle(values: &[Expense], k: usize, target: i128) -> Option<Vec<Expense>> {
+    let (first, second) = (k / 2, k - k / 2);
+    let sum = |indices: &[usize]| indices.iter().map(|&i| values[i] as i128).sum::<i128>();
+
+    let mut halves: HashMap<i128, Vec<Vec<usize>>> = HashMap::new();
+    for indices in (0..values.len()).combinations(first) {
+        halves.entry(sum(&indices)).or_default().push(indices);
+    }
+
+    for indices in (0..values.len()).combinations(second) {
+        let other = halves
+            .get(&(target - sum(&indices)))
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|candidate| candidate.iter().all(|i| !indices.contains(i)))
+            });
+
+        if let Some(other) = other {
+            return Some(other.iter().chain(&indices).map(|&i| values[i]).collect());
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-        1721\n\
-        979\n\
-        366\n\
-        299\n\
-        675\n\
-        1456\n\
-    ";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day1/example.txt");
 
     #[test]
     fn part1() {
-        let values = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(values);
-        assert_eq!(solution, 514579);
+        let values = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&values);
+        assert_eq!(solution, Ok(Some(514579)));
     }
 
     #[test]
     fn part2() {
-        let values = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part2(values);
-        assert_eq!(solution, 241861950);
+        let values = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&values);
+        assert_eq!(solution, Ok(Some(241861950)));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("1721\n97x\n").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (1, 2, 1));
+        assert_eq!(err.text, "97x");
+    }
+
+    #[test]
+    fn k_sums() {
+        let values = parse_input(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(find_k_sum(&values, 2, 2020), Some(vec![1721, 299]));
+        assert_eq!(find_k_sum(&values, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(find_k_sum(&values, 1, 979), Some(vec![979]));
+        assert_eq!(find_k_sum(&values, 2, 1), None);
+
+        let four = find_k_sum(&values, 4, 1721 + 979 + 366 + 299).unwrap();
+        assert_eq!(four.iter().sum::<Expense>(), 1721 + 979 + 366 + 299);
+
+        // an entry can't pair up with itself
+        assert_eq!(find_k_sum(&[1010], 2, 2020), None);
+        assert_eq!(find_k_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+    }
+
+    #[test]
+    fn large_input() {
+        let mut values: Vec<Expense> = (0..300_000).map(|i| 3_000 + i * 7).collect();
+        values.push(1_234);
+        values.push(786);
+
+        assert_eq!(compute_part1(&values), Ok(Some(1_234 * 786)));
+
+        // entries that can never reach the target are skipped rather than paired up
+        let values: Vec<Expense> = (0..300_000).map(|i| i % 1_000).collect();
+        assert_eq!(count_combinations(&values, 2, 2020), 0);
+        assert_eq!(count_combinations(&values, 3, 3000), 0);
+    }
+
+    #[test]
+    fn every_combination() {
+        let values = parse_input(EXAMPLE_INPUT).unwrap();
+
+        let pairs: Vec<Combination> = combinations(&values, 2, 2020).collect();
+        assert_eq!(
+            pairs,
+            vec![Combination {
+                indices: vec![0, 3],
+                entries: vec![1721, 299],
+            }]
+        );
+        assert_eq!(pairs[0].product(), Ok(514579));
+
+        // repeated entries are different entries
+        assert_eq!(count_combinations(&[1010, 1010, 1010], 2, 2020), 3);
+        assert_eq!(count_combinations(&[5, 1, 4, 2, 3], 2, 5), 2);
+        assert_eq!(count_combinations(&values, 3, 1), 0);
+        assert_eq!(count_combinations(&values, 0, 0), 1);
+    }
+
+    #[test]
+    fn no_matching_entries() {
+        assert_eq!(compute_part1(&[1, 2, 3]), Ok(None));
+        assert_eq!(compute_part2(&[]), Ok(None));
+    }
+
+    #[test]
+    fn refunds() {
+        let values = parse_input("3000\n-980\n1\n").unwrap();
+        assert_eq!(compute_part1(&values), Ok(Some(-2_940_000)));
+        assert_eq!(count_combinations(&values, 3, 2021), 1);
+        assert_eq!(find_k_sum(&[-5, 7, 2018], 2, 2013), Some(vec![-5, 2018]));
+    }
+
+    #[test]
+    fn overflow() {
+        let big = Expense::MAX / 2;
+        let values = vec![big, big, TARGET - 2 * big];
+
+        let err = compute_part2(&values).unwrap_err();
+        assert_eq!(err.entries.len(), 3);
+        assert!(err.to_string().ends_with("doesn't fit in 128 bits"));
+
+        // a pair always fits
+        assert!(compute_part1(&[Expense::MAX, TARGET - Expense::MAX]).is_ok());
+    }
+
+    #[test]
+    fn subsets() {
+        let values = parse_input(EXAMPLE_INPUT).unwrap();
+
+        let subset = subset_sum(&values, 2020, SubsetMode::Smallest).unwrap();
+        assert_eq!(subset.entries, vec![1721, 299]);
+        assert_eq!(subset.product(), Ok(514579));
+
+        let all: Expense = values.iter().sum();
+        let subset = subset_sum(&values, all, SubsetMode::Any).unwrap();
+        assert_eq!(subset.indices, vec![0, 1, 2, 3, 4, 5]);
+
+        // a refund can make for a smaller subset than the entries that add up without it
+        let subset = subset_sum(&[1, 2, 3, -4, 10], 6, SubsetMode::Smallest).unwrap();
+        assert_eq!(subset.entries, vec![-4, 10]);
+
+        assert_eq!(subset_sum(&values, 1, SubsetMode::Any), None);
+        let empty = subset_sum(&values, 0, SubsetMode::Smallest).unwrap();
+        assert!(empty.indices.is_empty());
+        assert!(subset_sum(&values, 0, SubsetMode::Any)
+            .unwrap()
+            .indices
+            .is_empty());
+
+        // far more entries than fit in an `i128` product
+        let values = vec![9999; 12];
+        let subset = subset_sum(&values, 9999 * 12, SubsetMode::Any).unwrap();
+        assert!(subset.product().is_err());
+        assert_eq!(
+            subset.exact_product(),
+            "998800659780049492080923920804949780006599880001"
+        );
+    }
+
+    #[test]
+    fn exact_products() {
+        assert_eq!(exact_product(&[1721, 299, -3]), "-1543737");
+        assert_eq!(exact_product(&[-5, 0, 7]), "0");
+        assert_eq!(exact_product(&[]), "1");
+        assert_eq!(
+            exact_product(&[Expense::MIN, Expense::MIN]),
+            (Expense::MIN as i128 * Expense::MIN as i128).to_string()
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        let stream =
+            |report: &str| find_pair_streaming(report.as_bytes(), &mut PairWatcher::new(TARGET));
+
+        let pair = stream(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            pair,
+            Some(LinePair {
+                lines: (1, 4),
+                entries: (1721, 299),
+            })
+        );
+        assert_eq!(pair.unwrap().product(), 514579);
+
+        // lines after the match are never read, so never fail to parse
+        let pair = stream("1721\n299\nnot a number\n").unwrap();
+        assert_eq!(pair.unwrap().lines, (1, 2));
+
+        match stream("1721\n97x\n299\n") {
+            Err(DayError::Parse(err)) => assert_eq!((err.line, err.text.as_str()), (2, "97x")),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        assert_eq!(stream("1\n2\n").unwrap(), None);
+
+        // a report that's still being written can be picked up again where it stopped
+        let mut watcher = PairWatcher::new(TARGET);
+        let pair = find_pair_streaming("1\n1721\n".as_bytes(), &mut watcher).unwrap();
+        assert_eq!(pair, None);
+        let pair = find_pair_streaming("5\n299\n".as_bytes(), &mut watcher).unwrap();
+        assert_eq!(pair.unwrap().lines, (2, 4));
+
+        // even when a read stops partway through a line
+        let mut watcher = PairWatcher::new(TARGET);
+        let pair = find_pair_streaming("1\n17".as_bytes(), &mut watcher).unwrap();
+        assert_eq!(pair, None);
+        let pair = find_pair_streaming("21\r\n299\n".as_bytes(), &mut watcher).unwrap();
+        assert_eq!(pair.unwrap().lines, (2, 3));
+
+        // a finished report doesn't need a newline after its last entry
+        let mut watcher = PairWatcher::new(TARGET);
+        let pair = find_pair_streaming("1721\n299".as_bytes(), &mut watcher).unwrap();
+        assert_eq!(pair, None);
+        assert_eq!(watcher.finish().unwrap().unwrap().lines, (1, 2));
+        assert_eq!(watcher.finish(), Ok(None));
+    }
+
+    #[test]
+    fn watching() {
+        let mut watcher = PairWatcher::new(TARGET);
+        assert_eq!(watcher.push(1010), None);
+        assert_eq!(watcher.push(5), None);
+
+        // entries can keep arriving after a report has been read through once
+        let pair = watcher.push(1010).unwrap();
+        assert_eq!((pair.lines, pair.entries), ((1, 3), (1010, 1010)));
+
+        let mut watcher = PairWatcher::new(TARGET);
+        assert_eq!(watcher.push_line("1721"), Ok(None));
+        let err = watcher.push_line("2 99").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "2 99"));
+
+        // the bad line still took up its place in the report
+        let pair = watcher.push_line("299").unwrap().unwrap();
+        assert_eq!(pair.lines, (1, 3));
     }
 }
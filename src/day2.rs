@@ -1,16 +1,47 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use parse_display::{Display as PDisplay, FromStr as PFromStr};
 
-pub fn parse_input(input: &str) -> Vec<Entry> {
-    input
-        .lines()
-        .map(|line| {
-            line.parse()
-                .expect("couldn't convert line to password entry")
-        })
-        .collect()
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Entry>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
 }
 
-pub fn compute_part1(entries: Vec<Entry>) -> usize {
+pub fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    util::parse_lines(Day2::DAY, input, |line| {
+        let entry: Entry = line
+            .parse()
+            .map_err(|_| LineError::new(1, "expected an entry like `1-3 a: abcde`"))?;
+
+        // positions are 1-based, so part 2 can't look up position 0
+        let max_column = line.find('-').map_or(1, |dash| dash + 2);
+        match (entry.min, entry.max) {
+            (0, _) => Err(LineError::new(1, "policy numbers start at 1")),
+            (_, 0) => Err(LineError::new(max_column, "policy numbers start at 1")),
+            _ => Ok(entry),
+        }
+    })
+}
+
+pub fn compute_part1(entries: &[Entry]) -> usize {
     entries
         .iter()
         .filter(|entry| {
@@ -20,7 +51,7 @@ pub fn compute_part1(entries: Vec<Entry>) -> usize {
         .count()
 }
 
-pub fn compute_part2(entries: Vec<Entry>) -> usize {
+pub fn compute_part2(entries: &[Entry]) -> usize {
     entries
         .iter()
         .filter(|entry| {
@@ -37,33 +68,45 @@ pub fn compute_part2(entries: Vec<Entry>) -> usize {
 #[derive(PDisplay, PFromStr, PartialEq, Debug)]
 #[display("{min}-{max} {character}: {password}")]
 pub struct Entry {
-    min: usize,
-    max: usize,
-    character: char,
-    password: String,
+    pub min: usize,
+    pub max: usize,
+    pub character: char,
+    pub password: String,
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-        1-3 a: abcde\n\
-        1-3 b: cdefg\n\
-        2-9 c: ccccccccc\
-    ";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day2/example.txt");
 
     #[test]
     fn part1() {
-        let entries = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(entries);
+        let entries = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&entries);
         assert_eq!(solution, 2);
     }
 
     #[test]
     fn part2() {
-        let entries = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part2(entries);
+        let entries = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&entries);
         assert_eq!(solution, 1);
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("1-3 a: abcde\n0-3 b: cdefg\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.reason, "policy numbers start at 1");
+
+        let err = parse_input("1-0 a: abc").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+
+        let err = parse_input("12-0 a: abc").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+
+        let err = parse_input("1-3 a abcde").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
 }
@@ -1,15 +1,64 @@
-pub fn parse_input(input: &str) -> Grid {
-    let rows = input.lines().map(|line| line.into()).collect();
-    Grid { rows }
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
+use std::fmt;
+use std::str::FromStr;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Grid;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
 }
 
-pub fn compute_part1(grid: Grid) -> usize {
-    count_trees(&grid, (3, 1))
+pub fn parse_input(input: &str) -> Result<Grid, ParseError> {
+    let mut row_width = None;
+
+    let rows = util::parse_lines(Day3::DAY, input, |line| {
+        let row: Row = line.parse()?;
+        if row.slots.is_empty() {
+            return Err(LineError::new(1, "expected at least one slot"));
+        }
+
+        let width = *row_width.get_or_insert(row.slots.len());
+
+        match row.slots.len() == width {
+            true => Ok(row),
+            false => Err(LineError::new(
+                1,
+                format!("expected a row {} slots wide", width),
+            )),
+        }
+    })?;
+
+    match rows.is_empty() {
+        true => Err(LineError::new(1, "grid should have at least one row").at(Day3::DAY, 0, "")),
+        false => Ok(Grid { rows }),
+    }
 }
 
-pub fn compute_part2(grid: Grid) -> usize {
+pub fn compute_part1(grid: &Grid) -> usize {
+    count_trees(grid, (3, 1))
+}
+
+pub fn compute_part2(grid: &Grid) -> usize {
     #[rustfmt::skip]
-    let slopes = vec![
+    let slopes = [
         (1, 1),
         (3, 1),
         (5, 1),
@@ -19,57 +68,70 @@ pub fn compute_part2(grid: Grid) -> usize {
 
     slopes
         .iter()
-        .map(|&slope| count_trees(&grid, slope))
+        .map(|&slope| count_trees(grid, slope))
         .product()
 }
 
-fn count_trees(grid: &Grid, step: (usize, usize)) -> usize {
+/// counts the trees hit going from the top left to the bottom of the grid, `step` at a time
+pub fn count_trees(grid: &Grid, step: (usize, usize)) -> usize {
     grid.iter((0, 0), step)
         .filter(|slot| matches!(slot, Slot::Tree))
         .count()
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 enum Slot {
     Tree,
     Open,
 }
 
-#[derive(Clone)]
+#[derive(Debug)]
 struct Row {
     slots: Vec<Slot>,
 }
 
-impl From<&str> for Row {
-    fn from(input: &str) -> Self {
+impl FromStr for Row {
+    type Err = LineError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         let slots = input
             .chars()
-            .map(|c| match c {
-                '.' => Slot::Open,
-                '#' => Slot::Tree,
-                _ => panic!("invalid input data"),
+            .enumerate()
+            .map(|(index, c)| match c {
+                '.' => Ok(Slot::Open),
+                '#' => Ok(Slot::Tree),
+                _ => Err(LineError::new(
+                    index + 1,
+                    format!("expected '.' or '#', found '{}'", c),
+                )),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Row { slots }
+        Ok(Row { slots })
     }
 }
 
+#[derive(Debug)]
 pub struct Grid {
     rows: Vec<Row>,
 }
 
 impl Grid {
-    fn iter(&self, start: (usize, usize), step: (usize, usize)) -> impl Iterator<Item = Slot> {
+    /// how many slots wide and how many rows tall the grid is
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows[0].slots.len(), self.rows.len())
+    }
+
+    fn iter(&self, start: (usize, usize), step: (usize, usize)) -> GridIter<'_> {
         let row_width = self
             .rows
-            .get(0)
+            .first()
             .expect("grid should have at least one row")
             .slots
             .len();
 
         GridIter {
-            rows: self.rows.clone(),
+            rows: &self.rows,
             current_x: start.0,
             current_y: start.1,
             step_x: step.0,
@@ -79,8 +141,25 @@ impl Grid {
     }
 }
 
-struct GridIter {
-    rows: Vec<Row>,
+/// draws the grid the same way the input does, one row per line
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            for slot in &row.slots {
+                match slot {
+                    Slot::Tree => write!(f, "#")?,
+                    Slot::Open => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct GridIter<'a> {
+    rows: &'a [Row],
     current_x: usize,
     current_y: usize,
     step_x: usize,
@@ -88,7 +167,7 @@ struct GridIter {
     row_width: usize,
 }
 
-impl Iterator for GridIter {
+impl Iterator for GridIter<'_> {
     type Item = Slot;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -101,10 +180,9 @@ impl Iterator for GridIter {
             .get(self.current_y)
             .and_then(|row| row.slots.get(self.current_x))
             .cloned()
-            .map(|slot| {
+            .inspect(|_| {
                 self.current_x = next_x;
                 self.current_y = next_y;
-                slot
             })
     }
 }
@@ -113,31 +191,28 @@ impl Iterator for GridIter {
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-        ..##.......\n\
-        #...#...#..\n\
-        .#....#..#.\n\
-        ..#.#...#.#\n\
-        .#...##..#.\n\
-        ..#.##.....\n\
-        .#.#.#....#\n\
-        .#........#\n\
-        #.##...#...\n\
-        #...##....#\n\
-        .#..#...#.#\n\
-    ";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day3/example.txt");
 
     #[test]
     fn part1() {
-        let grid = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(grid);
+        let grid = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&grid);
         assert_eq!(solution, 7);
     }
 
     #[test]
     fn part2() {
-        let grid = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part2(grid);
+        let grid = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&grid);
         assert_eq!(solution, 336);
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("..#\n.o.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = parse_input("..#\n.#\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
 }
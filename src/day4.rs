@@ -1,9 +1,56 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use itertools::Itertools;
 use regex::Regex;
 use std::str::Lines;
 
-pub fn parse_input(input: &str) -> Vec<String> {
-    input.lines().batching(get_next_group).collect()
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    util::parse_lines(Day4::DAY, input, check_fields)?;
+    Ok(input.lines().batching(get_next_group).collect())
+}
+
+/// every field on a line should look like `key:value`
+fn check_fields(line: &str) -> Result<(), LineError> {
+    let mut column = 1;
+
+    for field in line.split(' ') {
+        let valid = match field.split_once(':') {
+            Some((key, value)) => !key.is_empty() && !value.is_empty(),
+            None => field.is_empty(),
+        };
+
+        if !valid {
+            let reason = format!("expected a field like `key:value`, found `{}`", field);
+            return Err(LineError::new(column, reason));
+        }
+
+        column += field.chars().count() + 1;
+    }
+
+    Ok(())
 }
 
 fn get_next_group(lines: &mut Lines) -> Option<String> {
@@ -13,14 +60,13 @@ fn get_next_group(lines: &mut Lines) -> Option<String> {
 
     let group = lines
         .take_while(|line| !line.is_empty())
-        .map(|line| line.split_whitespace())
-        .flatten()
+        .flat_map(|line| line.split_whitespace())
         .join(" ");
 
     Some(group)
 }
 
-pub fn compute_part1(passports: Vec<String>) -> usize {
+pub fn compute_part1(passports: &[String]) -> usize {
     passports
         .iter()
         .filter(|p| {
@@ -35,7 +81,7 @@ pub fn compute_part1(passports: Vec<String>) -> usize {
         .count()
 }
 
-pub fn compute_part2(passports: Vec<String>) -> usize {
+pub fn compute_part2(passports: &[String]) -> usize {
     let match_byr = Regex::new(r"byr:(\d{4})\b").unwrap();
     let match_iyr = Regex::new(r"iyr:(\d{4})\b").unwrap();
     let match_eyr = Regex::new(r"eyr:(\d{4})\b").unwrap();
@@ -80,13 +126,13 @@ pub fn compute_part2(passports: Vec<String>) -> usize {
 }
 
 fn is_valid(byr: u32, iyr: u32, eyr: u32, hgt_in: Option<u32>, hgt_cm: Option<u32>) -> bool {
-    let in_valid = hgt_in.map(|i| i >= 59 && i <= 76);
-    let cm_valid = hgt_cm.map(|c| c >= 150 && c <= 193);
+    let in_valid = hgt_in.map(|i| (59..=76).contains(&i));
+    let cm_valid = hgt_cm.map(|c| (150..=193).contains(&c));
     let height_valid = in_valid.or(cm_valid).unwrap_or(false);
 
-    (byr >= 1920 && byr <= 2002)
-        && (iyr >= 2010 && iyr <= 2020)
-        && (eyr >= 2020 && eyr <= 2030)
+    (1920..=2002).contains(&byr)
+        && (2010..=2020).contains(&iyr)
+        && (2020..=2030).contains(&eyr)
         && height_valid
 }
 
@@ -94,62 +140,31 @@ fn is_valid(byr: u32, iyr: u32, eyr: u32, hgt_in: Option<u32>, hgt_cm: Option<u3
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT_1: &'static str = "\
-ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm
-
-iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929
-
-hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm
-
-hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in
-    ";
-
-    const EXAMPLE_INPUT_2: &'static str = "\
-eyr:1972 cid:100
-hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
-
-iyr:2019
-hcl:#602927 eyr:1967 hgt:170cm
-ecl:grn pid:012533040 byr:1946
-
-hcl:dab227 iyr:2012
-ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
-
-hgt:59cm ecl:zzz
-eyr:2038 hcl:74454a iyr:2023
-pid:3556412378 byr:2007
+    const EXAMPLE_INPUT_1: &str = include_str!("../examples/day4/example1.txt");
 
-pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-hcl:#623a2f
-
-eyr:2029 ecl:blu cid:129 byr:1989
-iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
-
-hcl:#888785
-hgt:164cm byr:2001 iyr:2015 cid:88
-pid:545766238 ecl:hzl
-eyr:2022
-
-iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
-    ";
+    const EXAMPLE_INPUT_2: &str = include_str!("../examples/day4/example2.txt");
 
     #[test]
     fn part1() {
-        let passports = parse_input(EXAMPLE_INPUT_1);
-        let solution = compute_part1(passports);
+        let passports = parse_input(EXAMPLE_INPUT_1).unwrap();
+        let solution = compute_part1(&passports);
         assert_eq!(solution, 2);
     }
 
     #[test]
     fn part2() {
-        let passports = parse_input(EXAMPLE_INPUT_2);
-        let solution = compute_part2(passports);
+        let passports = parse_input(EXAMPLE_INPUT_2).unwrap();
+        let solution = compute_part2(&passports);
         assert_eq!(solution, 4);
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("ecl:gry pid\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 9));
+        assert_eq!(err.reason, "expected a field like `key:value`, found `pid`");
+
+        let err = parse_input("byr:1937\n\niyr:2017 :147").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 10));
+    }
 }
@@ -1,54 +1,101 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use itertools::Itertools;
+use std::fmt;
+use std::str::FromStr;
 
-pub fn parse_input(input: &str) -> Vec<Seat> {
-    input.lines().map(|line| line.into()).collect()
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Input = Vec<Seat>;
+    type Output1 = Option<u32>;
+    type Output2 = Option<u32>;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
 }
 
-pub fn compute_part1(seats: Vec<Seat>) -> u32 {
-    seats
-        .iter()
-        .map(|seat| seat.id())
-        .max()
-        .expect("we have at least one seat")
+pub fn parse_input(input: &str) -> Result<Vec<Seat>, ParseError> {
+    util::parse_lines(Day5::DAY, input, |line| line.parse())
 }
 
-pub fn compute_part2(seats: Vec<Seat>) -> u32 {
+/// the highest seat id, unless there are no seats at all
+pub fn compute_part1(seats: &[Seat]) -> Option<u32> {
+    seats.iter().map(|seat| seat.id()).max()
+}
+
+/// the id missing between two taken seats, if there is such a gap
+pub fn compute_part2(seats: &[Seat]) -> Option<u32> {
     let sorted_ids: Vec<u32> = seats.iter().map(|seat| seat.id()).sorted().collect();
 
     sorted_ids
         .windows(2)
         .find(|pair| pair[0] + 2 == pair[1])
         .map(|pair| pair[0] + 1)
-        .expect("couldn't find gap in seats")
 }
 
+#[derive(Debug, PartialEq)]
 pub struct Seat {
-    row: u8,
-    col: u8,
+    pub row: u8,
+    pub col: u8,
 }
 
 impl Seat {
-    fn id(&self) -> u32 {
+    pub fn id(&self) -> u32 {
         self.row as u32 * 8 + self.col as u32
     }
 }
 
-impl From<&str> for Seat {
-    fn from(seat: &str) -> Self {
+impl FromStr for Seat {
+    type Err = LineError;
+
+    fn from_str(seat: &str) -> Result<Self, Self::Err> {
         let binary: String = seat
             .chars()
-            .map(|c| match c {
-                'F' | 'L' => '0',
-                'B' | 'R' => '1',
-                _ => panic!("invalid seat"),
+            .enumerate()
+            .map(|(index, c)| match (index, c) {
+                (0..=6, 'F') | (7..=9, 'L') => Ok('0'),
+                (0..=6, 'B') | (7..=9, 'R') => Ok('1'),
+                (0..=6, _) => Err(LineError::new(index + 1, "expected 'F' or 'B'")),
+                (7..=9, _) => Err(LineError::new(index + 1, "expected 'L' or 'R'")),
+                _ => Err(LineError::new(index + 1, "expected only 10 characters")),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        // unwraps are safe, b/c we know this is a binary string if we didn't panic above
+        if binary.len() < 10 {
+            return Err(LineError::new(binary.len() + 1, "expected 10 characters"));
+        }
+
+        // unwraps are safe, b/c we know this is a 10 digit binary string if we got here
         let row = u8::from_str_radix(&binary[0..7], 2).unwrap();
         let col = u8::from_str_radix(&binary[7..10], 2).unwrap();
 
-        Seat { row, col }
+        Ok(Seat { row, col })
+    }
+}
+
+/// encodes a seat the same way boarding passes do, e.g. `FBFBBFFRLR`
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = format!("{:07b}", self.row)
+            .replace('0', "F")
+            .replace('1', "B");
+        let col = format!("{:03b}", self.col)
+            .replace('0', "L")
+            .replace('1', "R");
+        write!(f, "{}{}", row, col)
     }
 }
 
@@ -56,16 +103,27 @@ impl From<&str> for Seat {
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-        BFFFBBFRRR\n\
-        FFFBBBFRRR\n\
-        BBFFBBFRLL\n\
-    ";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day5/example.txt");
 
     #[test]
     fn part1() {
-        let seats = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(seats);
-        assert_eq!(solution, 820);
+        let seats = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&seats);
+        assert_eq!(solution, Some(820));
+    }
+
+    #[test]
+    fn no_seats() {
+        assert_eq!(compute_part1(&[]), None);
+        assert_eq!(compute_part2(&[]), None);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("BFFFBBFRRR\nFFFBBLFRRR\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+
+        let err = parse_input("BFFFBBFRR\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 10));
     }
 }
@@ -1,9 +1,46 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::str::Lines;
 
-pub fn parse_input(input: &str) -> Vec<Group> {
-    input.lines().batching(get_next_group).collect()
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = Vec<Group>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Group>, ParseError> {
+    util::parse_lines(Day6::DAY, input, check_answers)?;
+    Ok(input.lines().batching(get_next_group).collect())
+}
+
+/// each person's answers are the questions (a-z) they answered "yes" to
+fn check_answers(line: &str) -> Result<(), LineError> {
+    match line.chars().position(|c| !c.is_ascii_lowercase()) {
+        Some(index) => Err(LineError::new(
+            index + 1,
+            "expected only questions a through z",
+        )),
+        None => Ok(()),
+    }
 }
 
 fn get_next_group(lines: &mut Lines) -> Option<Group> {
@@ -19,14 +56,14 @@ fn get_next_group(lines: &mut Lines) -> Option<Group> {
     Some(Group { answers })
 }
 
-pub fn compute_part1(groups: Vec<Group>) -> usize {
+pub fn compute_part1(groups: &[Group]) -> usize {
     groups
         .iter()
         .map(|group| get_unique_answers_for_group(group).len())
         .sum()
 }
 
-pub fn compute_part2(groups: Vec<Group>) -> usize {
+pub fn compute_part2(groups: &[Group]) -> usize {
     groups
         .iter()
         .map(|group| {
@@ -61,6 +98,7 @@ fn all_in_group_answered_yes(group: &Group, question: char) -> bool {
         .all(|curr_answers_list| curr_answers_list.contains(question))
 }
 
+#[derive(Debug)]
 pub struct Group {
     answers: Vec<String>,
 }
@@ -69,34 +107,26 @@ pub struct Group {
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-abc
-
-a
-b
-c
-
-ab
-ac
-
-a
-a
-a
-a
-
-b";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day6/example.txt");
 
     #[test]
     fn part1() {
-        let groups = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(groups);
+        let groups = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&groups);
         assert_eq!(solution, 11);
     }
 
     #[test]
     fn part2() {
-        let groups = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part2(groups);
+        let groups = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&groups);
         assert_eq!(solution, 6);
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("abc\n\naBc\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.reason, "expected only questions a through z");
+    }
 }
@@ -1,66 +1,173 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 
 pub type Rules = HashMap<String, Vec<(usize, String)>>;
 
-pub fn parse_input(input: &str) -> Rules {
-    input.lines().map(get_next_rule).collect()
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Input = Rules;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
 }
 
-fn get_next_rule(line: &str) -> (String, Vec<(usize, String)>) {
+pub fn parse_input(input: &str) -> Result<Rules, ParseError> {
+    let rules = util::parse_lines(Day7::DAY, input, get_next_rule)?;
+
+    // a bag that ends up inside itself would hold bags forever
+    if let Some(index) = find_cycle(&rules) {
+        let reason = format!("`{}` bags end up inside themselves", rules[index].0);
+        let line = input.lines().nth(index).unwrap_or_default();
+        return Err(LineError::new(1, reason).at(Day7::DAY, index, line));
+    }
+
+    Ok(rules.into_iter().collect())
+}
+
+/// the line of a rule whose bag ends up inside itself, if there is one; this walks the rules
+/// with its own stack, so a long chain of bags can't overflow the real one
+fn find_cycle(rules: &[(String, Vec<(usize, String)>)]) -> Option<usize> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        New,
+        Open,
+        Done,
+    }
+
+    let lines: HashMap<&str, usize> = rules
+        .iter()
+        .enumerate()
+        .map(|(index, (color, _))| (color.as_str(), index))
+        .collect();
+    let mut visits = vec![Visit::New; rules.len()];
+
+    for start in 0..rules.len() {
+        if visits[start] != Visit::New {
+            continue;
+        }
+
+        // each rule being looked inside, and how many of its contents have been so far
+        let mut stack = vec![(start, 0)];
+        visits[start] = Visit::Open;
+
+        while let Some(&(rule, next)) = stack.last() {
+            let inner = match rules[rule].1.get(next) {
+                Some((_, inner)) => inner,
+                None => {
+                    visits[rule] = Visit::Done;
+                    stack.pop();
+                    continue;
+                }
+            };
+
+            let top = stack.len() - 1;
+            stack[top].1 += 1;
+
+            if let Some(&inner) = lines.get(inner.as_str()) {
+                match visits[inner] {
+                    Visit::Open => return Some(inner),
+                    Visit::New => {
+                        visits[inner] = Visit::Open;
+                        stack.push((inner, 0));
+                    }
+                    Visit::Done => {}
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn get_next_rule(line: &str) -> Result<(String, Vec<(usize, String)>), LineError> {
     lazy_static! {
         // create this regex only the first time we try to use it
         // after that, re-use the existing one.
-        static ref CONTENT_MATCHER: Regex = Regex::new(r"(\d+?) (\D+? \D+?) bags?[,.]").unwrap();
+        static ref CONTENT_MATCHER: Regex = Regex::new(r"^(\d+) (\S+ \S+) bags?$").unwrap();
+    }
+
+    let (color, contents) = split_first(line, " bags contain ").ok_or_else(|| {
+        LineError::new(1, "expected a rule like `<color> bags contain <contents>`")
+    })?;
+    let mut column = line.len() - contents.len() + 1;
+
+    if contents == "no other bags." {
+        return Ok((color.to_string(), Vec::new()));
     }
 
-    let (color, contents) =
-        split_first(line, " bags contain ").expect("each line should contain a bag rule");
-
-    let contents: Vec<(usize, String)> = CONTENT_MATCHER
-        .captures_iter(contents)
-        .map(|captures| {
-            (
-                (&captures[1])
-                    .parse()
-                    .expect("bag count should be usize-able"),
-                (&captures[2]).to_string(),
-            )
+    let contents = contents
+        .strip_suffix('.')
+        .ok_or_else(|| LineError::new(line.len() + 1, "expected the rule to end with `.`"))?;
+
+    let contents: Vec<(usize, String)> = contents
+        .split(", ")
+        .map(|bag| {
+            let bag_column = column;
+            column += bag.len() + ", ".len();
+
+            let captures = CONTENT_MATCHER.captures(bag).ok_or_else(|| {
+                let reason = "expected contents like `2 muted yellow bags` or `no other bags`";
+                LineError::new(bag_column, reason)
+            })?;
+            let count = captures[1]
+                .parse()
+                .map_err(|_| LineError::new(bag_column, "bag count is too large"))?;
+
+            Ok((count, captures[2].to_string()))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    (color.to_string(), contents)
+    Ok((color.to_string(), contents))
 }
 
-fn split_first<'a, 'b>(src: &'a str, pattern: &'b str) -> Option<(&'a str, &'a str)> {
+fn split_first<'a>(src: &'a str, pattern: &str) -> Option<(&'a str, &'a str)> {
     src.find(pattern)
         .map(|idx| (&src[0..idx], &src[(idx + pattern.len())..]))
 }
 
-pub fn compute_part1(rules: Rules) -> usize {
+pub fn compute_part1(rules: &Rules) -> usize {
     rules
         .keys()
-        .filter(|&key| contains_shiny_gold_bag(&rules, key))
+        .filter(|&key| contains_bag(rules, key, "shiny gold"))
         .count()
 }
 
-pub fn compute_part2(rules: Rules) -> usize {
-    count_contents(&rules, "shiny gold")
+pub fn compute_part2(rules: &Rules) -> usize {
+    count_contents(rules, "shiny gold")
 }
 
-fn contains_shiny_gold_bag(rules: &Rules, bag: &str) -> bool {
+/// whether a `bag` holds a `target` bag, directly or inside some other bag
+pub fn contains_bag(rules: &Rules, bag: &str, target: &str) -> bool {
     rules
         .get(bag)
         .map(|contents| {
-            contents.iter().any(|(_, bag_name)| {
-                bag_name == "shiny gold" || contains_shiny_gold_bag(rules, bag_name)
-            })
+            contents
+                .iter()
+                .any(|(_, bag_name)| bag_name == target || contains_bag(rules, bag_name, target))
         })
         .unwrap_or(false)
 }
 
-fn count_contents(rules: &Rules, bag: &str) -> usize {
+/// how many bags a `bag` holds in total
+pub fn count_contents(rules: &Rules, bag: &str) -> usize {
     rules
         .get(bag)
         .map(|contents| {
@@ -76,37 +183,69 @@ fn count_contents(rules: &Rules, bag: &str) -> usize {
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT_1: &'static str = "\
-light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.";
-
-    const EXAMPLE_INPUT_2: &'static str = "\
-shiny gold bags contain 2 dark red bags.
-dark red bags contain 2 dark orange bags.
-dark orange bags contain 2 dark yellow bags.
-dark yellow bags contain 2 dark green bags.
-dark green bags contain 2 dark blue bags.
-dark blue bags contain 2 dark violet bags.
-dark violet bags contain no other bags.";
+    const EXAMPLE_INPUT_1: &str = include_str!("../examples/day7/example1.txt");
+
+    const EXAMPLE_INPUT_2: &str = include_str!("../examples/day7/example2.txt");
 
     #[test]
     fn part1() {
-        let rules = parse_input(EXAMPLE_INPUT_1);
-        let solution = compute_part1(rules);
+        let rules = parse_input(EXAMPLE_INPUT_1).unwrap();
+        let solution = compute_part1(&rules);
         assert_eq!(solution, 4);
     }
 
     #[test]
     fn part2() {
-        let rules = parse_input(EXAMPLE_INPUT_2);
-        let solution = compute_part2(rules);
+        let rules = parse_input(EXAMPLE_INPUT_2).unwrap();
+        let solution = compute_part2(&rules);
         assert_eq!(solution, 126);
     }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input(
+            "faded blue bags contain no other bags.\nlight red bags hold 1 faded blue bag.",
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = parse_input("light red bags contain 99999999999999999999 bright white bags.")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 24));
+        assert_eq!(err.reason, "bag count is too large");
+
+        let err = parse_input("light red bags contain garbage.").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 24));
+
+        let err = parse_input("light red bags contain 1 bright white bag, 2 muted yellow bags")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 63));
+
+        let err = parse_input("light red bags contain 1 bright white bag, two muted yellow bags.")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 44));
+    }
+
+    #[test]
+    fn cycles() {
+        let err = parse_input(
+            "shiny gold bags contain 1 light red bag.\n\
+             light red bags contain 1 dark blue bag.\n\
+             dark blue bags contain 1 light red bag.",
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.reason, "`light red` bags end up inside themselves");
+
+        assert!(parse_input("light red bags contain 3 light red bags.").is_err());
+
+        // a bag held by several others is fine, as long as none of it loops back
+        let rules = parse_input(
+            "light red bags contain 1 dark blue bag, 2 faded green bags.\n\
+             dark blue bags contain 1 faded green bag.\n\
+             faded green bags contain no other bags.",
+        )
+        .unwrap();
+        assert_eq!(count_contents(&rules, "light red"), 4);
+    }
 }
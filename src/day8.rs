@@ -1,21 +1,49 @@
+use crate::error::{LineError, ParseError};
+use crate::solution::Solution;
+use crate::util;
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
 
-pub fn parse_input(input: &str) -> Vec<Instruction> {
-    input.lines().map(|line| line.into()).collect()
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = Vec<Instruction>;
+    type Output1 = Result<i32, JumpOutOfBounds>;
+    type Output2 = Option<i32>;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
 }
 
-pub fn compute_part1(mut instructions: Vec<Instruction>) -> i32 {
-    let (_, acc) = run_program(&mut instructions);
-    acc
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    util::parse_lines(Day8::DAY, input, |line| line.parse())
 }
 
-pub fn compute_part2(instructions: Vec<Instruction>) -> i32 {
+pub fn compute_part1(instructions: &[Instruction]) -> Result<i32, JumpOutOfBounds> {
+    let (_, acc) = run_program(instructions)?;
+    Ok(acc)
+}
+
+/// the accumulator of the first program, with one `jmp` or `nop` swapped, that terminates
+pub fn compute_part2(instructions: &[Instruction]) -> Option<i32> {
     let mut index_of_last_change = -1isize;
 
     loop {
         // reset to a blank slate
-        let mut instructions = instructions.clone();
+        let mut instructions = instructions.to_vec();
 
         // find the next instruction to try swapping
         let instruction = instructions
@@ -41,35 +69,44 @@ pub fn compute_part2(instructions: Vec<Instruction>) -> i32 {
             break;
         }
 
-        let (terminated, acc) = run_program(&mut instructions);
-
-        if terminated {
-            return acc;
+        // jumping out of the program doesn't count as terminating
+        if let Ok((true, acc)) = run_program(&instructions) {
+            return Some(acc);
         }
     }
 
-    0
+    None
 }
 
-fn run_program(instructions: &mut Vec<Instruction>) -> (bool, i32) {
+/// runs the program until it either terminates by stepping just past its last instruction,
+/// or is about to run an instruction a second time; returns whether it terminated,
+/// along with the accumulator's final value
+pub fn run_program(instructions: &[Instruction]) -> Result<(bool, i32), JumpOutOfBounds> {
     let mut acc = 0;
     let mut index: isize = 0;
     let mut terminated = false;
 
+    // how many times each instruction has run so far
+    let mut hits = vec![0u32; instructions.len()];
+
     loop {
         if index as usize == instructions.len() {
             terminated = true;
             break;
         }
 
-        assert!(index >= 0 && (index as usize) < instructions.len());
-        let next = &mut instructions[index as usize];
+        if index < 0 || index as usize >= instructions.len() {
+            return Err(JumpOutOfBounds { index, acc });
+        }
+
+        let next = &instructions[index as usize];
+        let next_hits = &mut hits[index as usize];
 
-        if next.hits > 0 {
+        if *next_hits > 0 {
             break;
         }
 
-        next.hits += 1;
+        *next_hits += 1;
         match next.op {
             Op::Acc(arg) => {
                 acc += arg;
@@ -84,11 +121,28 @@ fn run_program(instructions: &mut Vec<Instruction>) -> (bool, i32) {
         }
     }
 
-    (terminated, acc)
+    Ok((terminated, acc))
 }
 
-#[derive(Copy, Clone, Debug)]
-enum Op {
+/// A jump to somewhere other than an instruction or just past the last one.
+#[derive(Debug, PartialEq)]
+pub struct JumpOutOfBounds {
+    pub index: isize,
+    pub acc: i32,
+}
+
+impl fmt::Display for JumpOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "jumped to instruction {}, outside the program (acc = {})",
+            self.index, self.acc
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Op {
     Acc(i32),
     Jmp(isize),
     Nop(isize),
@@ -96,31 +150,48 @@ enum Op {
 
 #[derive(Copy, Clone, Debug)]
 pub struct Instruction {
-    op: Op,
-    hits: u32,
+    pub op: Op,
 }
 
-impl From<&str> for Instruction {
-    fn from(input: &str) -> Self {
+impl FromStr for Instruction {
+    type Err = LineError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         lazy_static! {
             // create this regex only the first time we try to use it
             // after that, re-use the existing one.
             static ref OP_MATCHER: Regex = Regex::new(r"^(\D{3}) ([+-]?\d+?)$").unwrap();
         }
 
-        let captures = OP_MATCHER.captures(input).expect("invalid instruction");
-        let arg = (&captures[2])
+        let captures = OP_MATCHER
+            .captures(input)
+            .ok_or_else(|| LineError::new(1, "expected an instruction like `acc +1`"))?;
+        let arg = captures[2]
             .parse()
-            .expect("invalid instruction argument");
+            .map_err(|_| LineError::new(5, "argument is out of range"))?;
 
         let op = match &captures[1] {
             "acc" => Op::Acc(arg),
             "jmp" => Op::Jmp(arg as isize),
             "nop" => Op::Nop(arg as isize),
-            _ => unreachable!(),
+            other => {
+                let reason = format!("unknown operation `{}`", other);
+                return Err(LineError::new(1, reason));
+            }
         };
 
-        Instruction { op, hits: 0 }
+        Ok(Instruction { op })
+    }
+}
+
+/// writes an instruction the way the input does, e.g. `jmp -3`
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op {
+            Op::Acc(arg) => write!(f, "acc {:+}", arg),
+            Op::Jmp(arg) => write!(f, "jmp {:+}", arg),
+            Op::Nop(arg) => write!(f, "nop {:+}", arg),
+        }
     }
 }
 
@@ -128,28 +199,40 @@ impl From<&str> for Instruction {
 mod test {
     use super::*;
 
-    const EXAMPLE_INPUT: &'static str = "\
-nop +0
-acc +1
-jmp +4
-acc +3
-jmp -3
-acc -99
-acc +1
-jmp -4
-acc +6";
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day8/example.txt");
 
     #[test]
     fn part1() {
-        let instructions = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part1(instructions);
-        assert_eq!(solution, 5);
+        let instructions = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&instructions);
+        assert_eq!(solution, Ok(5));
     }
 
     #[test]
     fn part2() {
-        let instructions = parse_input(EXAMPLE_INPUT);
-        let solution = compute_part2(instructions);
-        assert_eq!(solution, 8);
+        let instructions = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&instructions);
+        assert_eq!(solution, Some(8));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = parse_input("nop +0\nmov +1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.reason, "unknown operation `mov`");
+    }
+
+    #[test]
+    fn jumping_out() {
+        let instructions = parse_input("acc +3\njmp -2").unwrap();
+        let err = compute_part1(&instructions).unwrap_err();
+        assert_eq!(err, JumpOutOfBounds { index: -1, acc: 3 });
+
+        // swapping the jump is the only way to terminate
+        assert_eq!(compute_part2(&instructions), Some(3));
+        assert_eq!(
+            compute_part2(&parse_input("acc +1\njmp -1\njmp -2").unwrap()),
+            None
+        );
     }
 }
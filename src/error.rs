@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::fmt;
+
+/// A problem found while parsing a day's input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    /// 1-based line number within the input
+    pub line: usize,
+    /// 1-based column within that line
+    pub column: usize,
+    /// the full text of the offending line
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day{}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.reason
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A problem within a single line; `util::parse_lines` fills in where that line is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    /// 1-based column within the line
+    pub column: usize,
+    pub reason: String,
+}
+
+impl LineError {
+    pub fn new<S: Into<String>>(column: usize, reason: S) -> Self {
+        LineError {
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    /// `line_index` is 0-based, as it comes out of `enumerate()`
+    pub fn at(self, day: u32, line_index: usize, text: &str) -> ParseError {
+        ParseError {
+            day,
+            line: line_index + 1,
+            column: self.column,
+            text: text.to_string(),
+            reason: self.reason,
+        }
+    }
+}
+
+/// Why a day couldn't produce any answers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DayError {
+    /// the input couldn't be read at all
+    Input(String),
+    Parse(ParseError),
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DayError::Input(reason) => write!(f, "{}", reason),
+            DayError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for DayError {}
+
+impl From<ParseError> for DayError {
+    fn from(err: ParseError) -> Self {
+        DayError::Parse(err)
+    }
+}
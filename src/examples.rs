@@ -0,0 +1,82 @@
+use crate::answers::{self, Answers};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A worked example from a day's puzzle description, along with the answers it gives.
+///
+/// Examples live in `examples/dayN/<name>.txt`, and their answers in
+/// `examples/dayN/answers.toml` under a `[<name>]` table with `part1`/`part2` keys.
+/// Examples often only cover one of the parts, so only parts with an answer are run.
+pub struct Example {
+    pub day: u32,
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+impl Example {
+    /// the parts this example has an answer for
+    pub fn parts(&self) -> Vec<u32> {
+        [1, 2]
+            .iter()
+            .copied()
+            .filter(|&part| self.answers.get(self.day, part).is_some())
+            .collect()
+    }
+}
+
+/// examples are part of the source tree, so they're found relative to it
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+/// every example for `day`, in name order; a day without any examples has none
+pub fn load(dir: &Path, day: u32) -> Result<Vec<Example>, String> {
+    let day_dir = dir.join(format!("day{}", day));
+    if !day_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let answers_path = day_dir.join(answers::FILE_NAME);
+    let tables = match answers_path.exists() {
+        true => answers::parse_tables(&read(&answers_path)?)
+            .map_err(|err| format!("invalid answers in {}: {}", answers_path.display(), err))?,
+        false => Default::default(),
+    };
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&day_dir)
+        .map_err(|err| format!("couldn't list {}: {}", day_dir.display(), err))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .expect("we only kept files ending in .txt")
+                .to_string_lossy()
+                .to_string();
+
+            let mut answers = Answers::default();
+            if let Some(parts) = tables.get(&name) {
+                for (part, answer) in answers::parse_parts(&name, parts)? {
+                    answers.insert(day, part, answer);
+                }
+            }
+
+            Ok(Example {
+                day,
+                input: read(path)?,
+                name,
+                answers,
+            })
+        })
+        .collect()
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("couldn't read {}: {}", path.display(), err))
+}
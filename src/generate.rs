@@ -0,0 +1,389 @@
+use crate::day5::Seat;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// A randomly generated puzzle input, along with any answers that were planted in it.
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Generated {
+    fn new(input: String) -> Self {
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+/// Generates a valid input for `day` with roughly `size` records (lines, passports,
+/// groups, bag rules, ...); the same `seed` always generates the same input.
+pub fn generate(day: u32, size: usize, seed: u64) -> Result<Generated, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = size.max(1);
+
+    match day {
+        1 => Ok(day1(&mut rng, size)),
+        2 => Ok(Generated::new(day2(&mut rng, size))),
+        3 => Ok(Generated::new(day3(&mut rng, size))),
+        4 => Ok(Generated::new(day4(&mut rng, size))),
+        5 => Ok(day5(&mut rng, size)),
+        6 => Ok(Generated::new(day6(&mut rng, size))),
+        7 => Ok(Generated::new(day7(&mut rng, size))),
+        8 => Ok(day8(&mut rng, size)),
+        _ => Err(format!("no generator for day{}", day)),
+    }
+}
+
+/// An expense report with exactly one pair and one triple of entries summing to 2020.
+/// Every other entry is larger than 2020, so it can't be part of either.
+fn day1(rng: &mut StdRng, size: usize) -> Generated {
+    let (pair, triple) = loop {
+        let a = rng.gen_range(1..2020);
+        let x = rng.gen_range(1..2019);
+        let y = rng.gen_range(1..2020 - x);
+        let pair = [a, 2020 - a];
+        let triple = [x, y, 2020 - x - y];
+
+        // make sure the planted entries don't form any other pair or triple between them
+        let planted: Vec<u32> = pair.iter().chain(triple.iter()).copied().collect();
+        let pairs = planted
+            .iter()
+            .tuple_combinations()
+            .filter(|&(a, b)| a + b == 2020)
+            .count();
+        let triples = planted
+            .iter()
+            .tuple_combinations()
+            .filter(|&(a, b, c)| a + b + c == 2020)
+            .count();
+
+        if pairs == 1 && triples == 1 {
+            break (pair, triple);
+        }
+    };
+
+    let mut values: Vec<u32> = pair.iter().chain(triple.iter()).copied().collect();
+    while values.len() < size {
+        values.push(rng.gen_range(2021..1_000_000));
+    }
+    values.shuffle(rng);
+
+    Generated {
+        input: lines(values),
+        part1: Some((pair[0] * pair[1]).to_string()),
+        part2: Some((triple[0] * triple[1] * triple[2]).to_string()),
+    }
+}
+
+/// Password entries whose policies sometimes match and sometimes don't.
+fn day2(rng: &mut StdRng, size: usize) -> String {
+    let entries = (0..size).map(|_| {
+        let min = rng.gen_range(1..10);
+        let max = rng.gen_range(min..=min + 10);
+        let character = random_letter(rng);
+
+        // mostly the policy's letter, so both parts have a fair chance of matching
+        let length = rng.gen_range(1..=max + 2);
+        let password: String = (0..length)
+            .map(|_| match rng.gen_bool(0.4) {
+                true => character,
+                false => random_letter(rng),
+            })
+            .collect();
+
+        format!("{}-{} {}: {}", min, max, character, password)
+    });
+
+    lines(entries)
+}
+
+/// A map `size` rows tall, with trees scattered across it.
+fn day3(rng: &mut StdRng, size: usize) -> String {
+    let width = rng.gen_range(10..=64);
+    let rows = (0..size).map(|_| {
+        (0..width)
+            .map(|_| if rng.gen_bool(0.2) { '#' } else { '.' })
+            .collect::<String>()
+    });
+
+    lines(rows)
+}
+
+/// A batch of passports, with fields that are sometimes invalid or missing.
+fn day4(rng: &mut StdRng, size: usize) -> String {
+    let passports = (0..size).map(|_| {
+        let mut fields = vec![
+            format!("byr:{}", rng.gen_range(1900..2020)),
+            format!("iyr:{}", rng.gen_range(2000..2030)),
+            format!("eyr:{}", rng.gen_range(2010..2040)),
+            match rng.gen_bool(0.5) {
+                true => format!("hgt:{}cm", rng.gen_range(140..200)),
+                false => format!("hgt:{}in", rng.gen_range(50..80)),
+            },
+            match rng.gen_bool(0.8) {
+                true => format!("hcl:#{:06x}", rng.gen_range(0..0x100_0000)),
+                false => format!("hcl:{:06x}", rng.gen_range(0..0x100_0000)),
+            },
+            format!(
+                "ecl:{}",
+                ["amb", "blu", "brn", "gry", "grn", "hzl", "oth", "xry"]
+                    .choose(rng)
+                    .unwrap()
+            ),
+            match rng.gen_bool(0.8) {
+                true => format!("pid:{:09}", rng.gen_range(0..1_000_000_000)),
+                false => format!("pid:{}", rng.gen_range(0..100_000_000)),
+            },
+            format!("cid:{}", rng.gen_range(100..350)),
+        ];
+
+        // drop a field now and then, and mix up the order they're listed in
+        if rng.gen_bool(0.3) {
+            let index = rng.gen_range(0..fields.len());
+            fields.remove(index);
+        }
+        fields.shuffle(rng);
+
+        // passports can be spread over several lines
+        fields
+            .iter()
+            .map(|field| {
+                let separator = if rng.gen_bool(0.3) { "\n" } else { " " };
+                format!("{}{}", field, separator)
+            })
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    });
+
+    passports.collect::<Vec<_>>().join("\n\n") + "\n"
+}
+
+/// Boarding passes for a block of consecutive seats, with exactly one missing.
+fn day5(rng: &mut StdRng, size: usize) -> Generated {
+    // seats need a neighbor on either side of the gap, and ids only go up to 1023
+    let count = size.clamp(3, 1023) as u32;
+    let first = rng.gen_range(0..=1024 - count - 1);
+    let gap = rng.gen_range(first + 1..first + count);
+
+    let mut ids: Vec<u32> = (first..=first + count).filter(|&id| id != gap).collect();
+    ids.shuffle(rng);
+
+    let passes = ids.iter().map(|id| Seat {
+        row: (id / 8) as u8,
+        col: (id % 8) as u8,
+    });
+
+    Generated {
+        input: lines(passes),
+        part1: Some((first + count).to_string()),
+        part2: Some(gap.to_string()),
+    }
+}
+
+/// Groups of people, along with the questions each of them answered "yes" to.
+fn day6(rng: &mut StdRng, size: usize) -> String {
+    let groups = (0..size).map(|_| {
+        let people = rng.gen_range(1..=5);
+        (0..people)
+            .map(|_| {
+                let mut questions: Vec<char> = ('a'..='z').collect();
+                questions.shuffle(rng);
+                questions.truncate(rng.gen_range(1..=26));
+                questions.into_iter().collect::<String>()
+            })
+            .join("\n")
+    });
+
+    groups.collect::<Vec<_>>().join("\n\n") + "\n"
+}
+
+/// Bag rules that never contain themselves, directly or otherwise, and always mention
+/// shiny gold bags. Bags are split into a handful of layers that can only contain bags
+/// from deeper layers, which keeps the number of bags inside any one bag manageable.
+fn day7(rng: &mut StdRng, size: usize) -> String {
+    const ADJECTIVES: [&str; 24] = [
+        "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+        "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy", "dusky", "mellow",
+        "bold", "soft", "deep", "hazy",
+    ];
+    const COLORS: [&str; 24] = [
+        "aqua", "beige", "black", "blue", "bronze", "brown", "coral", "crimson", "cyan", "gold",
+        "gray", "green", "indigo", "lime", "magenta", "maroon", "olive", "orange", "plum", "red",
+        "salmon", "teal", "violet", "white",
+    ];
+    const LAYERS: usize = 6;
+
+    let mut colors: Vec<String> = ADJECTIVES
+        .iter()
+        .cartesian_product(COLORS.iter())
+        .map(|(adjective, color)| format!("{} {}", adjective, color))
+        .filter(|color| color != "shiny gold")
+        .collect();
+    colors.shuffle(rng);
+    colors.truncate(size.clamp(LAYERS, ADJECTIVES.len() * COLORS.len() - 1) - 1);
+    colors.push("shiny gold".to_string());
+    colors.shuffle(rng);
+
+    // the first bag of each layer is one bag deeper than the first of the layer before it
+    let layer_of = |index: usize| index * LAYERS / colors.len();
+
+    let mut rules: Vec<String> = colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| {
+            let deeper: Vec<&String> = (0..colors.len())
+                .filter(|&other| layer_of(other) == layer_of(index) + 1)
+                .map(|other| &colors[other])
+                .collect();
+
+            let count = rng.gen_range(0..=4).min(deeper.len());
+            let contents = deeper
+                .choose_multiple(rng, count)
+                .map(|bag| {
+                    let count = rng.gen_range(1..=5);
+                    let plural = if count == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", count, bag, plural)
+                })
+                .join(", ");
+
+            match contents.is_empty() {
+                true => format!("{} bags contain no other bags.", color),
+                false => format!("{} bags contain {}.", color, contents),
+            }
+        })
+        .collect();
+    rules.shuffle(rng);
+
+    lines(rules)
+}
+
+/// A boot program with exactly one corrupted instruction: a `jmp` back to an earlier
+/// instruction that should have been a `nop`. Every other instruction only ever moves
+/// forward, and no other `jmp`/`nop` swap can get past the corrupted one, so swapping
+/// it back is the only fix.
+fn day8(rng: &mut StdRng, size: usize) -> Generated {
+    let length = size.max(2);
+    let corrupted = rng.gen_range(1..length);
+
+    let mut program = Vec::with_capacity(length);
+    // the instructions that actually run before reaching the corrupted one
+    let mut path = Vec::new();
+    // the accumulator's value once the fixed program terminates
+    let mut acc_fixed = 0;
+    let mut acc_at_corruption = 0;
+
+    let mut next_on_path = 0;
+    for index in 0..length {
+        let on_path = index == next_on_path;
+        if on_path && index < corrupted {
+            path.push(index);
+        }
+        if index == corrupted {
+            acc_at_corruption = acc_fixed;
+        }
+
+        let instruction = match index == corrupted {
+            // filled in once we know where it can jump back to
+            true => String::new(),
+            false => match rng.gen_range(0..3) {
+                0 => {
+                    let arg = rng.gen_range(-50..=50);
+                    if on_path {
+                        acc_fixed += arg;
+                    }
+                    format!("acc {:+}", arg)
+                }
+                // as a `jmp`, this would loop forever
+                1 => "nop +0".to_string(),
+                _ => {
+                    // jumps can't skip over the corrupted instruction, or past the end
+                    let limit = if index < corrupted { corrupted } else { length };
+                    let arg = rng.gen_range(1..=(limit - index).min(5));
+                    if on_path {
+                        next_on_path = index + arg;
+                    }
+                    format!("jmp {:+}", arg)
+                }
+            },
+        };
+
+        if on_path && !instruction.starts_with("jmp") {
+            next_on_path = index + 1;
+        }
+
+        program.push(instruction);
+    }
+
+    let target = *path
+        .choose(rng)
+        .expect("the first instruction is always on the path");
+    program[corrupted] = format!("jmp -{}", corrupted - target);
+
+    Generated {
+        input: lines(program),
+        part1: Some(acc_at_corruption.to_string()),
+        part2: Some(acc_fixed.to_string()),
+    }
+}
+
+fn random_letter(rng: &mut StdRng) -> char {
+    rng.gen_range(b'a'..=b'z') as char
+}
+
+fn lines<T: ToString, I: IntoIterator<Item = T>>(items: I) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution;
+
+    /// every generated input should parse, and its planted answers should be found
+    #[test]
+    fn planted_answers() {
+        // days scaffolded with `new-day` don't have a generator until someone writes one
+        let solvers = solution::registry()
+            .into_iter()
+            .filter(|solver| generate(solver.day(), 1, 0).is_ok());
+
+        for solver in solvers {
+            for seed in 0..20 {
+                let generated = generate(solver.day(), 50, seed).unwrap();
+                let report = solver.run(&generated.input, &[1, 2], 1).unwrap();
+
+                let expected = [&generated.part1, &generated.part2];
+                for (part, result) in report.parts() {
+                    if let Some(expected) = expected[part as usize - 1] {
+                        assert_eq!(
+                            &result.answer.to_string(),
+                            expected,
+                            "day{} part{}",
+                            solver.day(),
+                            part
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        let first = generate(7, 100, 42).unwrap().input;
+        let second = generate(7, 100, 42).unwrap().input;
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Overrides where each day's `dayN.txt` input is looked up.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// `<dir>/<day>.txt`
+    Dir(PathBuf),
+    /// a single file, regardless of the day
+    File(PathBuf),
+    Stdin,
+    /// input given directly on the command line
+    Text(String),
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path to a file
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(path.into()),
+        }
+    }
+
+    /// whether this source holds the input for every day, rather than for a single one
+    pub fn is_per_day(&self) -> bool {
+        matches!(self, InputSource::Dir(_))
+    }
+
+    pub fn read(&self, day: &str) -> Result<String, String> {
+        match self {
+            InputSource::Dir(dir) => read_file(&dir.join(format!("{}.txt", day))),
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| format!("couldn't read input from stdin: {}", err))?;
+                Ok(input)
+            }
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+/// the directory given, or the one named by `AOC_INPUTS_DIR`, or `inputs`
+pub fn inputs_dir(dir: Option<&str>) -> PathBuf {
+    match dir {
+        Some(dir) => dir.into(),
+        None => std::env::var_os(INPUTS_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| DEFAULT_INPUTS_DIR.into()),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read input from {}: {}", path.display(), err))
+}
@@ -0,0 +1,27 @@
+//! Solutions for Advent of Code 2020, along with the runner that drives them.
+//!
+//! Each `dayN` module exposes its `parse_input`, `compute_part1` and `compute_part2`,
+//! and implements `solution::Solution` so it can be found through `solution::registry()`.
+
+pub mod answer;
+pub mod answers;
+pub mod cli;
+pub mod config;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod error;
+pub mod examples;
+pub mod generate;
+pub mod input;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod util;
+pub mod watch;
@@ -1,14 +1,77 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod util;
+use aoc_2020::cli::{self, Command};
+use aoc_2020::{config, generate, repl, runner, scaffold, watch};
+use std::path::Path;
 
 fn main() {
-    build_runner!(run_days, day1, day2, day3, day4, day5, day6, day7, day8);
-    run_days();
+    let options = parse_options(std::env::args().skip(1).collect()).unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, cli::USAGE);
+        std::process::exit(2);
+    });
+
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    let result = match options.command {
+        Command::Run if options.watch => watch_day(&options),
+        Command::Run => runner::run_days(&options),
+        Command::NewDay(day) => new_day(day),
+        Command::Generate(day) => generate_input(day, &options),
+        Command::Repl(day) => repl::repl(day, &options.input_source()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// the command line's options, on top of any defaults from the config file
+fn parse_options(args: Vec<String>) -> Result<cli::Options, String> {
+    // a first pass just to find out which config file to use, if any
+    let explicit = cli::parse_args(args.clone())?.config;
+
+    let defaults = match config::path(explicit.as_deref()) {
+        Some(path) => config::load(&path)?,
+        None => cli::Options::default(),
+    };
+
+    cli::parse_args_with(defaults, args)
+}
+
+/// scaffolding and watching always target this crate's source tree, wherever we're run from
+fn crate_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn watch_day(options: &cli::Options) -> Result<(), String> {
+    match options.days.as_slice() {
+        [day] => watch::watch(crate_root(), *day, &options.input_source()),
+        _ => Err("--watch needs exactly one day".to_string()),
+    }
+}
+
+fn new_day(day: u32) -> Result<(), String> {
+    for path in scaffold::new_day(crate_root(), day)? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn generate_input(day: u32, options: &cli::Options) -> Result<(), String> {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let generated = generate::generate(day, options.size.unwrap_or(generate::DEFAULT_SIZE), seed)?;
+
+    // keep stdout to just the input, so it can be piped straight into a run
+    eprintln!("seed: {}", seed);
+    for (part, answer) in [(1, &generated.part1), (2, &generated.part2)].iter() {
+        if let Some(answer) = answer {
+            eprintln!("planted part{}: {}", part, answer);
+        }
+    }
+
+    print!("{}", generated.input);
+    Ok(())
 }
@@ -0,0 +1,673 @@
+use crate::answer::Answer;
+use crate::day8::{Instruction, Op};
+use crate::input::InputSource;
+use crate::solution::{self, Solution, Solver};
+use crate::util;
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8};
+use itertools::Itertools;
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+/// how many records `show` prints when it isn't told
+const DEFAULT_SHOW: usize = 10;
+
+const COMMANDS: &str = "\
+part1, part2    solve a part, and show how long it took
+stats           summarize the parsed input
+show [N]        print the first N parsed records (default: 10)
+help            print this message
+quit            leave the repl";
+
+/// What the repl can do with a day's parsed input, beyond solving it.
+trait Explore: Solution {
+    /// anything a day's queries need to remember between commands
+    type State: Default;
+
+    /// the day's own commands, in the same layout as `COMMANDS`
+    const QUERIES: &'static str = "";
+
+    /// each parsed record, one per entry
+    fn records(input: &Self::Input) -> Vec<String>;
+
+    fn stats(_input: &Self::Input) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// runs a day-specific `command`, or returns `None` if the day doesn't have one by that name
+    fn query(
+        _input: &Self::Input,
+        _state: &mut Self::State,
+        _command: &str,
+        _args: &[&str],
+    ) -> Option<Result<String, String>> {
+        None
+    }
+}
+
+/// A day's input, loaded into the repl, with its type erased.
+trait Session {
+    fn help(&self) -> String;
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+struct Loaded<S: Explore> {
+    input: S::Input,
+    state: S::State,
+}
+
+impl<S: Explore> Session for Loaded<S> {
+    fn help(&self) -> String {
+        match S::QUERIES {
+            "" => COMMANDS.to_string(),
+            queries => format!("{}\n\nday{}:\n{}", COMMANDS, S::DAY, queries),
+        }
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "part1" => Ok(solve(|| S::part1(&self.input).into())),
+            "part2" => Ok(solve(|| S::part2(&self.input).into())),
+            "stats" => Ok(stats(S::records(&self.input).len(), S::stats(&self.input))),
+            "show" => Ok(S::records(&self.input)
+                .iter()
+                .take(show_count(args)?)
+                .join("\n")),
+            "help" => Ok(self.help()),
+            _ => S::query(&self.input, &mut self.state, command, args)
+                .unwrap_or_else(|| Err(unknown(command))),
+        }
+    }
+}
+
+/// A registered day without an `Explore` impl, like one fresh from `new-day`;
+/// it can still be solved, and its records are just the input's lines.
+struct Generic {
+    solver: Box<dyn Solver>,
+    input: String,
+}
+
+impl Session for Generic {
+    fn help(&self) -> String {
+        COMMANDS.to_string()
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "part1" | "part2" => {
+                let part = if command == "part1" { 1 } else { 2 };
+                let report = self
+                    .solver
+                    .run(&self.input, &[part], 1)
+                    .map_err(|err| err.to_string())?;
+                let (_, result) = report.parts().next().expect("we asked for one part");
+                Ok(describe(result.answer.clone(), result.times[0]))
+            }
+            "stats" => Ok(stats(self.input.lines().count(), Vec::new())),
+            "show" => Ok(self.input.lines().take(show_count(args)?).join("\n")),
+            "help" => Ok(self.help()),
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+fn stats(records: usize, stats: Vec<(&'static str, String)>) -> String {
+    std::iter::once(("records", records.to_string()))
+        .chain(stats)
+        .map(|(name, value)| format!("{:<16}{}", name, value))
+        .join("\n")
+}
+
+fn show_count(args: &[&str]) -> Result<usize, String> {
+    match args {
+        [] => Ok(DEFAULT_SHOW),
+        [count] => count
+            .parse()
+            .map_err(|_| format!("invalid count: {}", count)),
+        _ => Err("usage: show [N]".to_string()),
+    }
+}
+
+fn unknown(command: &str) -> String {
+    format!("unknown command `{}` (try `help`)", command)
+}
+
+fn solve(part: impl FnOnce() -> Answer) -> String {
+    let (answer, elapsed) = util::time(part);
+    describe(answer, elapsed)
+}
+
+fn describe(answer: Answer, elapsed: Duration) -> String {
+    match answer {
+        Answer::Text(text) => format!("{}\n({:.2?})", text.trim_matches('\n'), elapsed),
+        answer => format!("{} ({:.2?})", answer, elapsed),
+    }
+}
+
+fn load<S: Explore + 'static>(input: &str) -> Result<Box<dyn Session>, String> {
+    let input = S::parse_input(input).map_err(|err| err.to_string())?;
+    Ok(Box::new(Loaded::<S> {
+        input,
+        state: Default::default(),
+    }))
+}
+
+fn session(day: u32, input: &str) -> Result<Box<dyn Session>, String> {
+    match day {
+        1 => load::<day1::Day1>(input),
+        2 => load::<day2::Day2>(input),
+        3 => load::<day3::Day3>(input),
+        4 => load::<day4::Day4>(input),
+        5 => load::<day5::Day5>(input),
+        6 => load::<day6::Day6>(input),
+        7 => load::<day7::Day7>(input),
+        8 => load::<day8::Day8>(input),
+        _ => match solution::find(day) {
+            Some(solver) => generic(solver, input),
+            None => Err(format!("there's no solution for day{} yet", day)),
+        },
+    }
+}
+
+fn generic(solver: Box<dyn Solver>, input: &str) -> Result<Box<dyn Session>, String> {
+    // parse once up front, so bad input is reported when it's loaded, like any other day
+    solver.run(input, &[], 1).map_err(|err| err.to_string())?;
+    Ok(Box::new(Generic {
+        solver,
+        input: input.to_string(),
+    }))
+}
+
+/// loads a day's input, then runs commands against it as they're typed, until `quit`
+pub fn repl(day: u32, source: &InputSource) -> Result<(), String> {
+    if *source == InputSource::Stdin {
+        return Err("the repl reads its commands from stdin, so can't read input from it".into());
+    }
+
+    let input = source.read(&format!("day{}", day))?;
+    let mut session = session(day, &input)?;
+    println!("loaded day{}; type `help` for commands", day);
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("day{}> ", day);
+        std::io::stdout().flush().map_err(|err| err.to_string())?;
+
+        let line = match lines.next() {
+            Some(line) => line.map_err(|err| format!("couldn't read command: {}", err))?,
+            None => break,
+        };
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => continue,
+            ["quit"] | ["exit"] => break,
+            [command, args @ ..] => match session.execute(command, args) {
+                Ok(output) => println!("{}", output),
+                Err(err) => println!("error: {}", err),
+            },
+        }
+    }
+
+    Ok(())
+}
+
+fn debug_records<T: std::fmt::Debug>(records: &[T]) -> Vec<String> {
+    records
+        .iter()
+        .map(|record| format!("{:?}", record))
+        .collect()
+}
+
+impl Explore for day1::Day1 {
+    type State = ();
+
+    const QUERIES: &'static str = "\
+combos K [SUM]  list every K entries that add up to SUM (default: 2020), by line
+count K [SUM]   count how many sets of K entries add up to SUM
+subset SUM      find the fewest entries, however many, that add up to SUM
+subset SUM any  find any entries that add up to SUM, which can be quicker";
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        debug_records(input)
+    }
+
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        vec![
+            ("smallest", input.iter().min().unwrap_or(&0).to_string()),
+            ("largest", input.iter().max().unwrap_or(&0).to_string()),
+        ]
+    }
+
+    fn query(
+        input: &Self::Input,
+        _state: &mut Self::State,
+        command: &str,
+        args: &[&str],
+    ) -> Option<Result<String, String>> {
+        if command == "subset" {
+            return Some(subset(input, args));
+        }
+        if command != "combos" && command != "count" {
+            return None;
+        }
+
+        let parsed = match args {
+            [k] => k.parse().ok().map(|k| (k, day1::TARGET)),
+            [k, sum] => k.parse().ok().zip(sum.parse().ok()),
+            _ => None,
+        };
+        let (k, sum) = match parsed {
+            Some(parsed) => parsed,
+            None => return Some(Err(format!("usage: {} K [SUM]", command))),
+        };
+
+        let combinations = day1::combinations(input, k, sum);
+        Some(
+            Ok(match command {
+                "count" => combinations.count().to_string(),
+                _ => combinations.map(|c| describe_combination(&c)).join("\n"),
+            })
+            .map(|output| match output.is_empty() {
+                true => "none".to_string(),
+                false => output,
+            }),
+        )
+    }
+}
+
+fn subset(input: &[day1::Expense], args: &[&str]) -> Result<String, String> {
+    let (sum, mode) = match args {
+        [sum] => (sum, day1::SubsetMode::Smallest),
+        [sum, "any"] => (sum, day1::SubsetMode::Any),
+        _ => return Err("usage: subset SUM [any]".to_string()),
+    };
+    let sum = sum.parse().map_err(|_| format!("invalid sum: {}", sum))?;
+
+    Ok(match day1::subset_sum(input, sum, mode) {
+        Some(subset) => describe_combination(&subset),
+        None => "none".to_string(),
+    })
+}
+
+fn describe_combination(combination: &day1::Combination) -> String {
+    // lines are numbered from 1, like everywhere else
+    let lines = combination.indices.iter().map(|index| index + 1).join(", ");
+    let entries = combination.entries.iter().join(" + ");
+    let product = combination.exact_product();
+    format!("lines {}: {} (product {})", lines, entries, product)
+}
+
+impl Explore for day2::Day2 {
+    type State = ();
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input.iter().map(|entry| entry.to_string()).collect()
+    }
+}
+
+impl Explore for day3::Day3 {
+    type State = ();
+
+    const QUERIES: &'static str =
+        "slope R D       count the trees hit going R right and D down each step";
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input.to_string().lines().map(str::to_string).collect()
+    }
+
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        let (width, height) = input.size();
+        let trees = input.to_string().matches('#').count();
+        vec![
+            ("size", format!("{}x{}", width, height)),
+            ("trees", trees.to_string()),
+        ]
+    }
+
+    fn query(
+        input: &Self::Input,
+        _state: &mut Self::State,
+        command: &str,
+        args: &[&str],
+    ) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("slope", [right, down]) => Some(
+                match (right.parse::<usize>(), down.parse::<usize>()) {
+                    // going past the grid's edge either way is the same as going around it
+                    // or straight off the bottom, and keeps the steps from overflowing
+                    (Ok(right), Ok(down)) if down > 0 => {
+                        let (width, height) = input.size();
+                        Ok((right % width.max(1), down.min(height.max(1))))
+                    }
+                    _ => Err(format!("invalid slope: {} {}", right, down)),
+                }
+                .map(|step| day3::count_trees(input, step).to_string()),
+            ),
+            ("slope", _) => Some(Err("usage: slope R D".to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl Explore for day4::Day4 {
+    type State = ();
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input.clone()
+    }
+}
+
+impl Explore for day5::Day5 {
+    type State = ();
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .map(|seat| {
+                format!(
+                    "{} (row {}, column {}, id {})",
+                    seat,
+                    seat.row,
+                    seat.col,
+                    seat.id()
+                )
+            })
+            .collect()
+    }
+}
+
+impl Explore for day6::Day6 {
+    type State = ();
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        debug_records(input)
+    }
+}
+
+impl Explore for day7::Day7 {
+    type State = ();
+
+    const QUERIES: &'static str = "\
+contains COLOR  list the bags that hold a COLOR bag, however deep down
+inside COLOR    count the bags inside a COLOR bag";
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .sorted()
+            .map(|(bag, contents)| {
+                let contents = match contents.len() {
+                    0 => "nothing".to_string(),
+                    _ => contents
+                        .iter()
+                        .map(|(count, bag)| format!("{} {}", count, bag))
+                        .join(", "),
+                };
+                format!("{}: {}", bag, contents)
+            })
+            .collect()
+    }
+
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        let empty = input
+            .values()
+            .filter(|contents| contents.is_empty())
+            .count();
+        vec![("empty bags", empty.to_string())]
+    }
+
+    fn query(
+        input: &Self::Input,
+        _state: &mut Self::State,
+        command: &str,
+        args: &[&str],
+    ) -> Option<Result<String, String>> {
+        let color = args.join(" ");
+        let known = |color: &str| match input.contains_key(color) {
+            true => Ok(()),
+            false => Err(format!("no rule for {} bags", color)),
+        };
+
+        match command {
+            "contains" => Some(known(&color).map(|_| {
+                let holders: Vec<&String> = input
+                    .keys()
+                    .filter(|bag| day7::contains_bag(input, bag, &color))
+                    .sorted()
+                    .collect();
+                format!("{} bags: {}", holders.len(), holders.iter().join(", "))
+            })),
+            "inside" => {
+                Some(known(&color).map(|_| day7::count_contents(input, &color).to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Where a program stepped through in the repl has got to.
+#[derive(Default)]
+struct Machine {
+    index: usize,
+    acc: i32,
+    hits: Vec<u32>,
+}
+
+impl Machine {
+    /// runs the next instruction, describing what it did, unless the program has finished
+    /// or the instruction has already run once
+    fn step(&mut self, program: &[Instruction]) -> Result<String, String> {
+        self.hits.resize(program.len(), 0);
+
+        let instruction = match program.get(self.index) {
+            Some(instruction) => instruction,
+            None => return Err(format!("the program has terminated (acc = {})", self.acc)),
+        };
+
+        if self.hits[self.index] > 0 {
+            return Err(format!(
+                "instruction {} would run a second time (acc = {})",
+                self.index, self.acc
+            ));
+        }
+
+        // nothing changes unless the instruction can actually run, so a machine that's
+        // stopped stays stopped in the same place
+        let index = self.index;
+        let next = match instruction.op {
+            Op::Jmp(arg) => index as isize + arg,
+            Op::Acc(_) | Op::Nop(_) => index as isize + 1,
+        };
+
+        if next < 0 || next as usize > program.len() {
+            return Err(format!(
+                "{}: {} jumps out of the program",
+                index, instruction
+            ));
+        }
+
+        self.hits[index] += 1;
+        if let Op::Acc(arg) = instruction.op {
+            self.acc += arg;
+        }
+        self.index = next as usize;
+
+        Ok(format!(
+            "{:>5}: {:<10}acc = {}, next = {}",
+            index,
+            instruction.to_string(),
+            self.acc,
+            self.index
+        ))
+    }
+}
+
+impl Explore for day8::Day8 {
+    type State = Machine;
+
+    const QUERIES: &'static str = "\
+step [N]        run the next N instructions (default: 1)
+reset           go back to the start of the program";
+
+    fn records(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| format!("{:>5}: {}", index, instruction))
+            .collect()
+    }
+
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        let count = |op: fn(&Op) -> bool| input.iter().filter(|i| op(&i.op)).count().to_string();
+        vec![
+            ("acc", count(|op| matches!(op, Op::Acc(_)))),
+            ("jmp", count(|op| matches!(op, Op::Jmp(_)))),
+            ("nop", count(|op| matches!(op, Op::Nop(_)))),
+        ]
+    }
+
+    fn query(
+        input: &Self::Input,
+        state: &mut Self::State,
+        command: &str,
+        args: &[&str],
+    ) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("step", _) => {
+                let steps = match args {
+                    [] => Ok(1),
+                    [steps] => steps
+                        .parse()
+                        .map_err(|_| format!("invalid count: {}", steps)),
+                    _ => Err("usage: step [N]".to_string()),
+                };
+
+                Some(steps.map(|steps| {
+                    let mut lines = Vec::new();
+                    for _ in 0..steps {
+                        match state.step(input) {
+                            Ok(line) => lines.push(line),
+                            // show how far we got before stopping
+                            Err(err) => {
+                                lines.push(format!("stopped: {}", err));
+                                break;
+                            }
+                        }
+                    }
+                    lines.join("\n")
+                }))
+            }
+            ("reset", []) => {
+                *state = Machine::default();
+                Some(Ok("back at instruction 0".to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(day: u32, input: &str, commands: &[&str]) -> Vec<Result<String, String>> {
+        let mut session = session(day, input).unwrap();
+        commands
+            .iter()
+            .map(|line| {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                session.execute(words[0], &words[1..])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn common_commands() {
+        let input = include_str!("../examples/day1/example.txt");
+        let output = run(1, input, &["part1", "show 2", "stats", "bogus"]);
+
+        assert!(output[0].as_ref().unwrap().starts_with("514579 ("));
+        assert_eq!(output[1].as_ref().unwrap(), "1721\n979");
+        assert!(output[2].as_ref().unwrap().starts_with("records         6"));
+        assert!(output[3].is_err());
+    }
+
+    #[test]
+    fn bag_queries() {
+        let input = include_str!("../examples/day7/example1.txt");
+        let output = run(
+            7,
+            input,
+            &["contains shiny gold", "inside shiny gold", "inside plaid"],
+        );
+
+        assert!(output[0].as_ref().unwrap().starts_with("4 bags: "));
+        assert_eq!(output[1].as_ref().unwrap(), "32");
+        assert!(output[2].is_err());
+    }
+
+    #[test]
+    fn generic_session() {
+        let input = include_str!("../examples/day1/example.txt");
+        let mut session = generic(Box::new(day1::Day1), input).unwrap();
+
+        assert_eq!(session.help(), COMMANDS);
+        assert!(session
+            .execute("part1", &[])
+            .unwrap()
+            .starts_with("514579 ("));
+        assert_eq!(session.execute("show", &["2"]).unwrap(), "1721\n979");
+        assert_eq!(session.execute("stats", &[]).unwrap(), "records         6");
+        assert!(session.execute("combos", &["2"]).is_err());
+
+        assert!(generic(Box::new(day1::Day1), "12\nabc\n").is_err());
+    }
+
+    #[test]
+    fn steep_slopes() {
+        let input = include_str!("../examples/day3/example.txt");
+        let output = run(
+            3,
+            input,
+            &[
+                "slope 3 1",
+                "slope 14 1",
+                "slope 18446744073709551615 1",
+                "slope 1 99999",
+            ],
+        );
+
+        assert_eq!(output[0].as_ref().unwrap(), "7");
+        assert_eq!(output[1].as_ref().unwrap(), "7");
+        assert!(output[2].is_ok());
+        assert_eq!(output[3].as_ref().unwrap(), "0");
+    }
+
+    #[test]
+    fn stepping() {
+        let input = include_str!("../examples/day8/example.txt");
+        let output = run(8, input, &["step 2", "step 10", "reset", "step"]);
+
+        let first = output[0].as_ref().unwrap();
+        assert_eq!(first.lines().count(), 2);
+        assert!(first.ends_with("acc = 1, next = 2"));
+
+        let second = output[1].as_ref().unwrap();
+        assert!(second.ends_with("stopped: instruction 1 would run a second time (acc = 5)"));
+
+        assert!(output[3].as_ref().unwrap().ends_with("acc = 0, next = 1"));
+
+        // jumping out stops the machine where it was, however many more steps it's given
+        let output = run(8, "acc +2\njmp -2", &["step 3", "step"]);
+        assert!(output[0]
+            .as_ref()
+            .unwrap()
+            .ends_with("stopped: 1: jmp -2 jumps out of the program"));
+        assert_eq!(
+            output[1].as_ref().unwrap(),
+            "stopped: 1: jmp -2 jumps out of the program"
+        );
+    }
+}
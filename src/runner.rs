@@ -0,0 +1,714 @@
+use crate::answer::Answer;
+use crate::answers::{Answers, Verdict};
+use crate::cli::{Format, Options};
+use crate::error::DayError;
+use crate::examples;
+use crate::input::InputSource;
+use crate::solution::{self, Report, Solver};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// The outcome of running a single day.
+struct DayRun {
+    day: u32,
+    name: String,
+    result: Result<Report, DayError>,
+}
+
+pub fn run_days(options: &Options) -> Result<(), String> {
+    let solvers = select_solvers(options)?;
+    if options.example {
+        return run_examples(&solvers, options);
+    }
+
+    let answers = match options.check {
+        true => Some(Answers::load(&options.answers_path())?),
+        false => None,
+    };
+
+    let source = options.input_source();
+    if !source.is_per_day() && solvers.len() != 1 {
+        return Err("a single input can only be used when running exactly one day".to_string());
+    }
+
+    let mut runs = Vec::new();
+    let mut finish_day = |run: DayRun| {
+        match options.format {
+            Format::Text => print_day(&run, answers.as_ref()),
+            Format::Json => println!("{}", day_to_json(&run, answers.as_ref())),
+        }
+
+        runs.push(run);
+    };
+
+    match options.jobs {
+        Some(jobs) => run_parallel(&solvers, &source, options, jobs, finish_day)?,
+        None => solvers
+            .iter()
+            .for_each(|solver| finish_day(run_day(solver.as_ref(), &source, options))),
+    }
+
+    if options.format == Format::Text {
+        let reports: Vec<(&str, &Report)> = runs
+            .iter()
+            .filter_map(|run| {
+                run.result
+                    .as_ref()
+                    .ok()
+                    .map(|report| (run.name.as_str(), report))
+            })
+            .collect();
+
+        match options.bench {
+            Some(samples) => print_bench_table(&reports, samples),
+            None if !options.no_timings => print_timing_table(&reports),
+            None => {}
+        }
+
+        if runs.len() > 1 {
+            print_summary(&runs, options.parts().len(), answers.as_ref());
+        }
+    }
+
+    let failures = runs.iter().filter(|run| failed(run)).count();
+    if failures > 0 {
+        return Err(format!("{} of {} days failed", failures, runs.len()));
+    }
+
+    let wrong_answers = match &answers {
+        Some(answers) => runs
+            .iter()
+            .flat_map(|run| verdicts(run, answers))
+            .filter(|verdict| matches!(verdict, Verdict::Fail { .. }))
+            .count(),
+        None => 0,
+    };
+
+    match wrong_answers {
+        0 => Ok(()),
+        _ => Err(format!("{} answer(s) didn't match", wrong_answers)),
+    }
+}
+
+/// runs each day against its examples instead of its real input, checking every answer
+fn run_examples(solvers: &[Box<dyn Solver>], options: &Options) -> Result<(), String> {
+    let dir = examples::default_dir();
+    let mut failures = 0;
+
+    for solver in solvers {
+        for example in examples::load(&dir, solver.day())? {
+            let parts: Vec<u32> = example
+                .parts()
+                .into_iter()
+                .filter(|part| options.parts().contains(part))
+                .collect();
+
+            let run = DayRun {
+                day: solver.day(),
+                name: format!("{} ({})", solver.name(), example.name),
+                result: solver
+                    .run(&example.input, &parts, options.bench.unwrap_or(1))
+                    .map_err(DayError::from),
+            };
+
+            match options.format {
+                Format::Text => print_day(&run, Some(&example.answers)),
+                Format::Json => println!("{}", day_to_json(&run, Some(&example.answers))),
+            }
+
+            let wrong_answer = verdicts(&run, &example.answers)
+                .any(|verdict| matches!(verdict, Verdict::Fail { .. }));
+
+            if failed(&run) || wrong_answer {
+                failures += 1;
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{} example(s) failed", failures)),
+    }
+}
+
+/// looks up every requested day up front, so an unknown day is reported before anything runs
+fn select_solvers(options: &Options) -> Result<Vec<Box<dyn Solver>>, String> {
+    if options.days.is_empty() {
+        return Ok(solution::registry());
+    }
+
+    options
+        .days
+        .iter()
+        .map(|&day| solution::find(day).ok_or_else(|| unknown_day(day)))
+        .collect()
+}
+
+fn unknown_day(day: u32) -> String {
+    let available = solution::registry()
+        .iter()
+        .map(|solver| solver.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("unknown day: day{} (available: {})", day, available)
+}
+
+fn run_day(solver: &dyn Solver, source: &InputSource, options: &Options) -> DayRun {
+    let name = solver.name();
+    let result = source
+        .read(&name)
+        .map_err(DayError::Input)
+        .and_then(|input| {
+            let report = solver.run(&input, &options.parts(), options.bench.unwrap_or(1))?;
+            Ok(report)
+        });
+
+    DayRun {
+        day: solver.day(),
+        name,
+        result,
+    }
+}
+
+/// runs every day on a pool of `jobs` threads, handing each finished day to `finish_day`
+/// in the same order as `solvers`, as soon as it and every day before it are done
+fn run_parallel<F: FnMut(DayRun)>(
+    solvers: &[Box<dyn Solver>],
+    source: &InputSource,
+    options: &Options,
+    jobs: usize,
+    mut finish_day: F,
+) -> Result<(), String> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|err| format!("couldn't start thread pool: {}", err))?;
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            pool.install(|| {
+                solvers
+                    .par_iter()
+                    .enumerate()
+                    .for_each_with(sender, |sender, (index, solver)| {
+                        let run = run_day(solver.as_ref(), source, options);
+                        // the receiver only goes away if the main thread panicked
+                        let _ = sender.send((index, run));
+                    })
+            })
+        });
+
+        // days can finish out of order, so hold on to them until it's their turn
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+
+        for (index, run) in receiver {
+            finished.insert(index, run);
+
+            while let Some(run) = finished.remove(&next) {
+                finish_day(run);
+                next += 1;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// whether a day couldn't be run, or one of its parts couldn't come up with an answer
+fn failed(run: &DayRun) -> bool {
+    match &run.result {
+        Ok(report) => report
+            .parts()
+            .any(|(_, part)| matches!(part.answer, Answer::Error(_) | Answer::None)),
+        Err(_) => true,
+    }
+}
+
+/// how each answer in `run` compares with the recorded ones
+fn verdicts<'a>(run: &'a DayRun, answers: &'a Answers) -> impl Iterator<Item = Verdict> + 'a {
+    run.result
+        .iter()
+        .flat_map(|report| report.parts())
+        .map(move |(part, result)| answers.check(run.day, part, &result.answer))
+}
+
+/// prints a day's answers, or a diagnostic if its input couldn't be parsed;
+/// when `answers` are given, each answer is followed by whether it matches the recorded one
+fn print_day(run: &DayRun, answers: Option<&Answers>) {
+    println!("{}:", run.name);
+
+    let report = match &run.result {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("    error: {}", err.to_string().replace('\n', "\n    "));
+            return;
+        }
+    };
+
+    for (part, result) in report.parts() {
+        let verdict = answers.map(|answers| answers.check(run.day, part, &result.answer));
+
+        match (&result.answer, verdict) {
+            // text answers go on their own lines, so any ascii art lines up
+            (Answer::Text(text), verdict) => {
+                let verdict = verdict.map(|verdict| format!(" {}", verdict));
+                println!("    part{}:{}", part, verdict.unwrap_or_default());
+                for line in text.trim_matches('\n').lines() {
+                    println!("        {}", line);
+                }
+            }
+            (answer, Some(verdict)) => println!("    part{}: {} {}", part, answer, verdict),
+            (answer, None) => println!("    part{}: {}", part, answer),
+        }
+    }
+}
+
+fn day_to_json(run: &DayRun, answers: Option<&Answers>) -> Value {
+    let mut json = json!({
+        "day": run.day,
+        "name": run.name,
+        "parse": null,
+        "part1": null,
+        "part2": null,
+        "error": null,
+    });
+
+    match &run.result {
+        Ok(report) => {
+            json["parse"] = timings_to_json(&report.parse);
+
+            for (part, result) in report.parts() {
+                let mut part_json = json!({
+                    "answer": result.answer.to_json(),
+                    "time": timings_to_json(&result.times),
+                    "error": null,
+                });
+
+                if let Answer::Error(reason) = &result.answer {
+                    part_json["error"] = json!({
+                        "kind": "part",
+                        "reason": reason,
+                    });
+                }
+
+                if let Some(answers) = answers {
+                    let (status, expected) = match answers.check(run.day, part, &result.answer) {
+                        Verdict::Pass => ("pass", None),
+                        Verdict::Fail { expected } => ("fail", Some(expected.to_json())),
+                        Verdict::Unknown => ("unknown", None),
+                    };
+                    part_json["check"] = json!({ "status": status, "expected": expected });
+                }
+
+                json[format!("part{}", part)] = part_json;
+            }
+        }
+        Err(DayError::Input(reason)) => {
+            json["error"] = json!({
+                "kind": "input",
+                "reason": reason,
+            });
+        }
+        Err(DayError::Parse(err)) => {
+            json["error"] = json!({
+                "kind": "parse",
+                "line": err.line,
+                "column": err.column,
+                "text": err.text,
+                "reason": err.reason,
+            });
+        }
+    }
+
+    json
+}
+
+fn timings_to_json(times: &[Duration]) -> Value {
+    let (min, median, max) = summarize(times);
+
+    json!({
+        "runs": times.len(),
+        "min_ns": min.as_nanos() as u64,
+        "median_ns": median.as_nanos() as u64,
+        "max_ns": max.as_nanos() as u64,
+    })
+}
+
+fn print_timing_table(reports: &[(&str, &Report)]) {
+    println!();
+    println!("timings:");
+    println!(
+        "    {:<8}{:>12}{:>12}{:>12}",
+        "day", "parse", "part1", "part2"
+    );
+
+    for (day, report) in reports {
+        let part1 = report.part1.as_ref().map(|part| part.times[0]);
+        let part2 = report.part2.as_ref().map(|part| part.times[0]);
+
+        println!(
+            "    {:<8}{:>12}{:>12}{:>12}",
+            day,
+            format_duration(Some(report.parse[0])),
+            format_duration(part1),
+            format_duration(part2),
+        );
+    }
+}
+
+fn print_bench_table(reports: &[(&str, &Report)], samples: usize) {
+    println!();
+    println!("timings over {} runs:", samples);
+    println!(
+        "    {:<8}{:<8}{:>12}{:>12}{:>12}",
+        "day", "phase", "min", "median", "max"
+    );
+
+    for (day, report) in reports {
+        let phases = [
+            Some(("parse", &report.parse)),
+            report.part1.as_ref().map(|part| ("part1", &part.times)),
+            report.part2.as_ref().map(|part| ("part2", &part.times)),
+        ];
+
+        for (phase, times) in phases.iter().flatten() {
+            let (min, median, max) = summarize(times);
+            println!(
+                "    {:<8}{:<8}{:>12}{:>12}{:>12}",
+                day,
+                phase,
+                format_duration(Some(min)),
+                format_duration(Some(median)),
+                format_duration(Some(max)),
+            );
+        }
+    }
+}
+
+/// prints how many days and parts were solved, what failed, where the time went,
+/// and a star for each solved part, laid out like the advent calendar
+fn print_summary(runs: &[DayRun], parts_per_day: usize, answers: Option<&Answers>) {
+    let mut stars = BTreeMap::new();
+    let mut failures = Vec::new();
+    let mut part_times = Vec::new();
+    let mut total = Duration::default();
+
+    for run in runs {
+        let report = match &run.result {
+            Ok(report) => report,
+            Err(DayError::Input(_)) => {
+                failures.push(format!("{} (input)", run.name));
+                continue;
+            }
+            Err(DayError::Parse(_)) => {
+                failures.push(format!("{} (parse)", run.name));
+                continue;
+            }
+        };
+
+        total += summarize(&report.parse).1;
+
+        for (part, result) in report.parts() {
+            let time = summarize(&result.times).1;
+            total += time;
+            part_times.push((format!("{} part{}", run.name, part), time));
+
+            let verdict = answers.map(|answers| answers.check(run.day, part, &result.answer));
+            match (&result.answer, verdict) {
+                (Answer::Error(_), _) => {
+                    failures.push(format!("{} part{} (error)", run.name, part))
+                }
+                (Answer::None, _) => {
+                    failures.push(format!("{} part{} (no answer)", run.name, part))
+                }
+                (_, Some(Verdict::Fail { .. })) => {
+                    failures.push(format!("{} part{}", run.name, part))
+                }
+                _ => *stars.entry(run.day).or_insert(0) += 1,
+            }
+        }
+    }
+
+    let solved_days = runs
+        .iter()
+        .filter(|run| stars.get(&run.day) == Some(&parts_per_day))
+        .count();
+    let solved_parts: usize = stars.values().sum();
+
+    part_times.sort_by_key(|(_, time)| std::cmp::Reverse(*time));
+    let slowest = part_times
+        .iter()
+        .take(3)
+        .map(|(part, time)| format!("{} ({})", part, format_duration(Some(*time))))
+        .collect::<Vec<_>>();
+
+    println!();
+    println!("summary:");
+    println!("    {:<16}{} of {}", "days solved", solved_days, runs.len());
+    println!(
+        "    {:<16}{} of {}",
+        "parts solved",
+        solved_parts,
+        runs.len() * parts_per_day
+    );
+    println!("    {:<16}{}", "failures", list_or_none(&failures));
+    println!("    {:<16}{}", "slowest parts", list_or_none(&slowest));
+    println!("    {:<16}{}", "total time", format_duration(Some(total)));
+    println!();
+
+    for line in star_chart(&stars).lines() {
+        println!("    {}", line);
+    }
+}
+
+fn list_or_none(items: &[String]) -> String {
+    match items.len() {
+        0 => "none".to_string(),
+        _ => items.join(", "),
+    }
+}
+
+/// every day of the calendar, with a `*` for each part solved on that day
+fn star_chart(stars: &BTreeMap<u32, usize>) -> String {
+    let days = (1..=25)
+        .map(|day| format!("{:>3}", day))
+        .collect::<String>();
+    let solved = (1..=25)
+        .map(|day| match stars.get(&day) {
+            Some(2) => " **",
+            Some(1) => "  *",
+            _ => "  .",
+        })
+        .collect::<String>();
+
+    format!("{}\n{}\n", days, solved)
+}
+
+/// the min, median and max of a non-empty set of timings
+fn summarize(times: &[Duration]) -> (Duration, Duration, Duration) {
+    let mut sorted = times.to_vec();
+    sorted.sort();
+
+    (
+        sorted[0],
+        sorted[sorted.len() / 2],
+        sorted[sorted.len() - 1],
+    )
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.2?}", duration),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::LineError;
+    use crate::error::ParseError;
+    use crate::solution::PartReport;
+    use std::sync::{Arc, Condvar, Mutex};
+
+    fn run(result: Result<Report, DayError>) -> DayRun {
+        DayRun {
+            day: 7,
+            name: "day7".to_string(),
+            result,
+        }
+    }
+
+    #[test]
+    fn stars() {
+        let stars = vec![(1, 2), (2, 1), (25, 2)].into_iter().collect();
+        let chart = star_chart(&stars);
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert!(lines[0].starts_with("  1  2  3"));
+        assert!(lines[1].starts_with(" **  *  ."));
+        assert!(lines[1].ends_with("  . **"));
+        assert_eq!(lines[0].len(), lines[1].len());
+    }
+
+    #[test]
+    fn json_answers() {
+        let report = Report {
+            parse: vec![Duration::from_nanos(300), Duration::from_nanos(100)],
+            part1: Some(PartReport {
+                answer: Answer::Integer(4),
+                times: vec![Duration::from_nanos(20)],
+            }),
+            part2: Some(PartReport {
+                answer: Answer::Error("too big".to_string()),
+                times: vec![Duration::from_nanos(50)],
+            }),
+        };
+        let mut answers = Answers::default();
+        answers.insert(7, 1, Answer::Integer(5));
+
+        let json = day_to_json(&run(Ok(report)), Some(&answers));
+        assert_eq!((&json["day"], &json["name"]), (&json!(7), &json!("day7")));
+        assert_eq!(json["error"], Value::Null);
+        assert_eq!(
+            json["parse"],
+            json!({ "runs": 2, "min_ns": 100, "median_ns": 300, "max_ns": 300 })
+        );
+
+        assert_eq!(json["part1"]["answer"], json!(4));
+        assert_eq!(json["part1"]["error"], Value::Null);
+        assert_eq!(
+            json["part1"]["check"],
+            json!({ "status": "fail", "expected": 5 })
+        );
+
+        assert_eq!(json["part2"]["answer"], Value::Null);
+        assert_eq!(
+            json["part2"]["error"],
+            json!({ "kind": "part", "reason": "too big" })
+        );
+        assert_eq!(
+            json["part2"]["check"],
+            json!({ "status": "unknown", "expected": null })
+        );
+
+        let json = day_to_json(
+            &run(Ok(Report {
+                parse: vec![Duration::from_nanos(100)],
+                part1: None,
+                part2: None,
+            })),
+            None,
+        );
+        assert_eq!(
+            (&json["part1"], &json["part2"]),
+            (&Value::Null, &Value::Null)
+        );
+    }
+
+    #[test]
+    fn json_errors() {
+        let err = LineError::new(5, "expected a number").at(7, 1, "1-3 a");
+        let json = day_to_json(&run(Err(DayError::Parse(err))), None);
+        assert_eq!(
+            json["error"],
+            json!({
+                "kind": "parse",
+                "line": 2,
+                "column": 5,
+                "text": "1-3 a",
+                "reason": "expected a number",
+            })
+        );
+        assert_eq!(
+            (&json["parse"], &json["part1"]),
+            (&Value::Null, &Value::Null)
+        );
+
+        let reason = "couldn't read inputs/day7.txt".to_string();
+        let json = day_to_json(&run(Err(DayError::Input(reason))), None);
+        assert_eq!(
+            json["error"],
+            json!({ "kind": "input", "reason": "couldn't read inputs/day7.txt" })
+        );
+    }
+
+    /// A day that won't finish until every later day has, so days finish in reverse order.
+    struct Reversed {
+        day: u32,
+        days: u32,
+        /// the days finished so far, in the order they finished
+        finished: Arc<(Mutex<Vec<u32>>, Condvar)>,
+    }
+
+    impl Solver for Reversed {
+        fn day(&self) -> u32 {
+            self.day
+        }
+
+        fn run(&self, _input: &str, _parts: &[u32], _samples: usize) -> Result<Report, ParseError> {
+            let (finished, changed) = &*self.finished;
+            let later = (self.days - self.day) as usize;
+
+            // give up waiting eventually, so a broken pool fails the test instead of hanging it
+            let (mut finished, _) = changed
+                .wait_timeout_while(finished.lock().unwrap(), Duration::from_secs(10), |days| {
+                    days.len() < later
+                })
+                .unwrap();
+            finished.push(self.day);
+            changed.notify_all();
+
+            Ok(Report {
+                parse: vec![Duration::default()],
+                part1: None,
+                part2: None,
+            })
+        }
+    }
+
+    #[test]
+    fn parallel_days_finish_in_order() {
+        let finished = Arc::new((Mutex::new(Vec::new()), Condvar::new()));
+        let solvers: Vec<Box<dyn Solver>> = (1..=3)
+            .map(|day| {
+                Box::new(Reversed {
+                    day,
+                    days: 3,
+                    finished: finished.clone(),
+                }) as Box<dyn Solver>
+            })
+            .collect();
+
+        let mut handed = Vec::new();
+        let source = InputSource::Text(String::new());
+        run_parallel(&solvers, &source, &Options::default(), 3, |run| {
+            handed.push(run.day)
+        })
+        .unwrap();
+
+        assert_eq!(*finished.0.lock().unwrap(), vec![3, 2, 1]);
+        assert_eq!(handed, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unknown_days() {
+        // the first day without a solution yet, so scaffolding it doesn't break this test
+        let missing = (1..=25).find(|&day| solution::find(day).is_none()).unwrap();
+        let options = Options {
+            days: vec![1, missing],
+            ..Options::default()
+        };
+
+        let available = solution::registry()
+            .iter()
+            .map(|solver| solver.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        assert_eq!(
+            select_solvers(&options).err(),
+            Some(format!(
+                "unknown day: day{} (available: {})",
+                missing, available
+            ))
+        );
+        assert!(available.starts_with("day1, day2, "));
+
+        let options = Options {
+            days: vec![3, 1],
+            ..Options::default()
+        };
+        let days: Vec<u32> = select_solvers(&options)
+            .unwrap()
+            .iter()
+            .map(|solver| solver.day())
+            .collect();
+        assert_eq!(days, vec![3, 1]);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::answers;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// the starting point for a new day's module; `{day}` is replaced with the day's number
+const TEMPLATE: &str = r#"use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::util;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u32 = {day};
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        compute_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        compute_part2(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    util::parse_lines(Day{day}::DAY, input, |line| Ok(line.to_string()))
+}
+
+pub fn compute_part1(_lines: &[String]) -> usize {
+    // TODO: solve part 1
+    0
+}
+
+pub fn compute_part2(_lines: &[String]) -> usize {
+    // TODO: solve part 2
+    0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // TODO: paste the puzzle's example into this file, and its answers into
+    // examples/day{day}/answers.toml
+    const EXAMPLE_INPUT: &str = include_str!("../examples/day{day}/example.txt");
+
+    #[test]
+    #[ignore = "waiting on the example input"]
+    fn part1() {
+        let lines = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part1(&lines);
+        assert_eq!(solution, 0);
+    }
+
+    #[test]
+    #[ignore = "waiting on the example input"]
+    fn part2() {
+        let lines = parse_input(EXAMPLE_INPUT).unwrap();
+        let solution = compute_part2(&lines);
+        assert_eq!(solution, 0);
+    }
+}
+"#;
+
+/// the starting point for a new day's example answers
+const EXAMPLE_ANSWERS: &str = "[example]\n# part1 = \n# part2 = \n";
+
+/// Creates `src/dayN.rs` from the template along with an empty `inputs/dayN.txt` and
+/// an example stub in `examples/dayN/`, then registers the new module in `src/lib.rs`
+/// and `src/solution.rs`.
+/// Returns every file that was created or changed.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module_path = root.join("src").join(format!("day{}.rs", day));
+    let input_path = root.join("inputs").join(format!("day{}.txt", day));
+    let examples_dir = root.join("examples").join(format!("day{}", day));
+    let example_path = examples_dir.join("example.txt");
+    let example_answers_path = examples_dir.join(answers::FILE_NAME);
+    let lib_path = root.join("src").join("lib.rs");
+    let solution_path = root.join("src").join("solution.rs");
+
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    // work out every change before writing anything, so a failure leaves the tree untouched
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let solution = register_solver(&read(&solution_path)?, day)?;
+
+    write(&module_path, &TEMPLATE.replace("{day}", &day.to_string()))?;
+    write(&lib_path, &lib)?;
+    write(&solution_path, &solution)?;
+
+    let mut changed = vec![module_path, lib_path, solution_path];
+
+    fs::create_dir_all(&examples_dir)
+        .map_err(|err| format!("couldn't create {}: {}", examples_dir.display(), err))?;
+
+    // don't clobber inputs or examples that were put in place before the module
+    let stubs = [
+        (input_path, ""),
+        (example_path, ""),
+        (example_answers_path, EXAMPLE_ANSWERS),
+    ];
+    for (path, contents) in stubs.iter() {
+        if !path.exists() {
+            write(path, contents)?;
+            changed.push(path.clone());
+        }
+    }
+
+    Ok(changed)
+}
+
+/// adds `pub mod dayN;` to `lib`, keeping the day modules in order
+fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    insert_in_order(lib, day, &format!("pub mod day{};", day), |line| {
+        line.strip_prefix("pub mod day")?.strip_suffix(';')
+    })
+}
+
+/// adds `dayN::DayN` to the solver registry in `solution`, keeping the days in order
+fn register_solver(solution: &str, day: u32) -> Result<String, String> {
+    let entry = format!("        Box::new(crate::day{0}::Day{0}),", day);
+
+    insert_in_order(solution, day, &entry, |line| {
+        line.trim_start()
+            .strip_prefix("Box::new(crate::day")?
+            .split("::")
+            .next()
+    })
+}
+
+/// inserts `new_line` among the lines for which `day_of` finds a day number,
+/// right after the last one for an earlier day
+fn insert_in_order<F>(contents: &str, day: u32, new_line: &str, day_of: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<&str>,
+{
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, day_of(line)?.parse().ok()?)))
+        .collect();
+
+    if days.iter().any(|&(_, existing)| existing == day) {
+        return Err(format!("day{} is already registered", day));
+    }
+
+    let index = match days.iter().rev().find(|&&(_, existing)| existing < day) {
+        Some(&(index, _)) => index + 1,
+        None => days
+            .first()
+            .map(|&(index, _)| index)
+            .ok_or("couldn't find where the day modules are registered")?,
+    };
+
+    lines.insert(index, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("couldn't read {}: {}", path.display(), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("couldn't write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let lib = "pub mod cli;\npub mod day1;\npub mod day3;\npub mod error;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "pub mod cli;\npub mod day1;\npub mod day2;\npub mod day3;\npub mod error;\n"
+        );
+        assert!(register_module(lib, 3).is_err());
+
+        let solution = "    vec![\n        Box::new(crate::day1::Day1),\n    ]\n";
+        assert_eq!(
+            register_solver(solution, 10).unwrap(),
+            "    vec![\n        Box::new(crate::day1::Day1),\n        Box::new(crate::day10::Day10),\n    ]\n"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::util;
+use std::time::Duration;
+
+/// The contract each day's module fulfills so the runner can drive it.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    fn parse_input(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+/// An object-safe view of a `Solution`, with its input erased and its answers made `Answer`s,
+/// so days with different types can live side by side in the registry.
+pub trait Solver: Send + Sync {
+    fn day(&self) -> u32;
+
+    /// parses `input` and solves the requested `parts` from that one parsed value,
+    /// repeating every phase `samples` times to collect timings
+    fn run(&self, input: &str, parts: &[u32], samples: usize) -> Result<Report, ParseError>;
+
+    fn name(&self) -> String {
+        format!("day{}", self.day())
+    }
+}
+
+impl<S: Solution + Send + Sync> Solver for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, parts: &[u32], samples: usize) -> Result<Report, ParseError> {
+        let samples = samples.max(1);
+
+        let mut parse = Vec::with_capacity(samples);
+        let mut parsed = None;
+        for _ in 0..samples {
+            let (value, elapsed) = util::time(|| S::parse_input(input));
+            parse.push(elapsed);
+            parsed = Some(value?);
+        }
+        let parsed = parsed.expect("we parse at least once");
+
+        let part1 = match parts.contains(&1) {
+            true => Some(run_part(&parsed, samples, S::part1)),
+            false => None,
+        };
+        let part2 = match parts.contains(&2) {
+            true => Some(run_part(&parsed, samples, S::part2)),
+            false => None,
+        };
+
+        Ok(Report {
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+fn run_part<I, O: Into<Answer>>(parsed: &I, samples: usize, solve: fn(&I) -> O) -> PartReport {
+    let mut times = Vec::with_capacity(samples);
+    let mut answer = None;
+
+    for _ in 0..samples {
+        let (value, elapsed) = util::time(|| solve(parsed));
+        times.push(elapsed);
+        answer = Some(value);
+    }
+
+    PartReport {
+        answer: answer.expect("we solve at least once").into(),
+        times,
+    }
+}
+
+/// The answers a `Solver` produced, along with how long each phase took.
+pub struct Report {
+    pub parse: Vec<Duration>,
+    pub part1: Option<PartReport>,
+    pub part2: Option<PartReport>,
+}
+
+impl Report {
+    /// the parts that were run, along with their part number
+    pub fn parts(&self) -> impl Iterator<Item = (u32, &PartReport)> {
+        let part1 = self.part1.as_ref().map(|part| (1, part));
+        let part2 = self.part2.as_ref().map(|part| (2, part));
+        part1.into_iter().chain(part2)
+    }
+}
+
+pub struct PartReport {
+    pub answer: Answer,
+    pub times: Vec<Duration>,
+}
+
+/// every day we have a solution for, in order
+pub fn registry() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(crate::day1::Day1),
+        Box::new(crate::day2::Day2),
+        Box::new(crate::day3::Day3),
+        Box::new(crate::day4::Day4),
+        Box::new(crate::day5::Day5),
+        Box::new(crate::day6::Day6),
+        Box::new(crate::day7::Day7),
+        Box::new(crate::day8::Day8),
+    ]
+}
+
+pub fn find(day: u32) -> Option<Box<dyn Solver>> {
+    registry().into_iter().find(|solver| solver.day() == day)
+}
@@ -1,28 +1,21 @@
-pub fn get_input(day: &str) -> String {
-    let path = format!("inputs/{}.txt", day);
-    std::fs::read_to_string(path).unwrap_or_else(|_| {
-        panic!("missing input for {}", day);
-    })
-}
-
-#[macro_export]
-macro_rules! build_runner {
-    ( $name: ident, $( $day_module: ident),* ) => {
-        fn $name () {
-            $(
-                let day = stringify!($day_module);
-                println!("{}:", day);
+use crate::error::{LineError, ParseError};
+use std::time::{Duration, Instant};
 
-                let input = util::get_input(&day);
-
-                let parsed = $day_module::parse_input(&input);
-                let solution = $day_module::compute_part1(parsed);
-                println!("    part1: {}", solution);
+/// runs `f`, returning its result along with how long it took
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
 
-                let parsed = $day_module::parse_input(&input);
-                let solution = $day_module::compute_part2(parsed);
-                println!("    part2: {}", solution);
-            )*
-        }
-    }
+/// parses each line of `input` with `parse_line`, stopping at the first line that fails
+pub fn parse_lines<T, F>(day: u32, input: &str, mut parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, LineError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(line).map_err(|err| err.at(day, index, line)))
+        .collect()
 }
@@ -0,0 +1,172 @@
+use crate::input::InputSource;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// how often to check the watched files for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Re-runs a day's tests and its real input whenever its module or input changes,
+/// showing how the answers differ from the previous run. Runs until interrupted.
+///
+/// Changes to the source need a rebuild, so each run goes through `cargo` in `root`
+/// rather than calling the solver in this process.
+pub fn watch(root: &Path, day: u32, source: &InputSource) -> Result<(), String> {
+    let name = format!("day{}", day);
+    let input_path = match source {
+        InputSource::Dir(dir) => dir.join(format!("{}.txt", name)),
+        InputSource::File(path) => path.clone(),
+        _ => return Err("--watch needs its input in a file".to_string()),
+    };
+    // cargo runs from `root`, so relative paths have to be resolved from here first
+    let input_path = std::env::current_dir()
+        .map_err(|err| format!("couldn't find the current directory: {}", err))?
+        .join(input_path);
+    let watched = [
+        root.join("src").join(format!("{}.rs", name)),
+        input_path.clone(),
+    ];
+
+    let mut previous = HashMap::new();
+    let mut last_modified = modified_times(&watched);
+
+    loop {
+        println!("== {} ==", name);
+        previous = run_once(root, &name, &input_path, &previous)?;
+        println!("watching {} for changes...", describe_paths(&watched));
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let modified = modified_times(&watched);
+            if modified != last_modified {
+                last_modified = modified;
+                break;
+            }
+        }
+    }
+}
+
+/// runs the day's tests, then the day itself, printing each answer next to the previous one
+fn run_once(
+    root: &Path,
+    name: &str,
+    input_path: &Path,
+    previous: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let tests = Command::new("cargo")
+        .args(["test", "--quiet", "--lib", &format!("{}::", name)])
+        .current_dir(root)
+        .status()
+        .map_err(|err| format!("couldn't run cargo test: {}", err))?;
+
+    println!("tests: {}", if tests.success() { "ok" } else { "FAILED" });
+
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--", name, "--format", "json", "--input"])
+        .arg(input_path)
+        .current_dir(root)
+        .output()
+        .map_err(|err| format!("couldn't run cargo run: {}", err))?;
+
+    // a failed build or a bad input explains itself on stderr
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    for part in &["part1", "part2"] {
+        if let Some(answer) = answers.get(*part) {
+            println!(
+                "    {}: {}",
+                part,
+                describe_change(answer, previous.get(*part))
+            );
+        }
+    }
+
+    Ok(answers)
+}
+
+/// pulls each part's answer out of the runner's json output, leaving out parts that
+/// failed with an error instead
+fn parse_answers(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .flat_map(|day| {
+            ["part1", "part2"]
+                .iter()
+                .filter_map(|part| {
+                    if !day[*part]["error"].is_null() {
+                        return None;
+                    }
+
+                    let answer = match &day[*part]["answer"] {
+                        Value::Null => return None,
+                        Value::String(answer) => answer.clone(),
+                        answer => answer.to_string(),
+                    };
+                    Some((part.to_string(), answer))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn describe_change(answer: &str, previous: Option<&String>) -> String {
+    match previous {
+        Some(previous) if previous == answer => format!("{} (unchanged)", answer),
+        Some(previous) => format!("{} (was {})", answer, previous),
+        None => answer.to_string(),
+    }
+}
+
+/// missing files count as unmodified, so the watcher waits for them to show up
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| path.metadata().and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+fn describe_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answers_from_json() {
+        let output = r#"{"day":4,"part1":{"answer":242},"part2":null}"#;
+        let answers = parse_answers(output);
+
+        assert_eq!(answers.get("part1").map(String::as_str), Some("242"));
+        assert_eq!(answers.get("part2"), None);
+
+        let output = r#"{"day":1,"part1":{"answer":"abc","error":null},"part2":{"answer":null,"error":{"kind":"part","reason":"too big"}}}"#;
+        let answers = parse_answers(output);
+
+        assert_eq!(answers.get("part1").map(String::as_str), Some("abc"));
+        assert_eq!(answers.get("part2"), None);
+    }
+
+    #[test]
+    fn changes() {
+        assert_eq!(describe_change("186", None), "186");
+        assert_eq!(
+            describe_change("186", Some(&"186".to_string())),
+            "186 (unchanged)"
+        );
+        assert_eq!(
+            describe_change("187", Some(&"186".to_string())),
+            "187 (was 186)"
+        );
+    }
+}
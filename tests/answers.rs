@@ -0,0 +1,28 @@
+use aoc_2020::answers::{Answers, Verdict};
+use aoc_2020::input::InputSource;
+use aoc_2020::solution;
+
+/// every day's real input should still produce its recorded answers
+#[test]
+fn real_inputs() {
+    let answers = Answers::load("inputs/answers.toml".as_ref()).unwrap();
+    let source = InputSource::Dir("inputs".into());
+
+    for solver in solution::registry() {
+        let input = source.read(&solver.name()).unwrap();
+        let report = solver.run(&input, &[1, 2], 1).unwrap();
+
+        for (part, result) in report.parts() {
+            // days that are still in progress won't have their answers recorded yet
+            if let Verdict::Fail { expected } = answers.check(solver.day(), part, &result.answer) {
+                panic!(
+                    "{} part{}: expected {}, got {}",
+                    solver.name(),
+                    part,
+                    expected,
+                    result.answer
+                );
+            }
+        }
+    }
+}
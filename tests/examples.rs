@@ -0,0 +1,23 @@
+use aoc_2020::answers::Verdict;
+use aoc_2020::examples;
+use aoc_2020::solution;
+
+/// every day's examples should produce the answers given in its puzzle description
+#[test]
+fn all_examples() {
+    let dir = examples::default_dir();
+
+    for solver in solution::registry() {
+        for example in examples::load(&dir, solver.day()).unwrap() {
+            let case = format!("{}/{}", solver.name(), example.name);
+            let report = solver
+                .run(&example.input, &example.parts(), 1)
+                .unwrap_or_else(|err| panic!("{}: {}", case, err));
+
+            for (part, result) in report.parts() {
+                let verdict = example.answers.check(solver.day(), part, &result.answer);
+                assert_eq!(verdict, Verdict::Pass, "{} part{}", case, part);
+            }
+        }
+    }
+}
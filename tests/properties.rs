@@ -0,0 +1,326 @@
+use aoc_2020::day2::Entry;
+use aoc_2020::day5::Seat;
+use aoc_2020::generate::{self, Generated};
+use aoc_2020::{day1, day2, day3, day4, day5, day6, day7, day8};
+use itertools::Itertools;
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+fn generate(day: u32, size: usize, seed: u64) -> Generated {
+    generate::generate(day, size, seed).unwrap()
+}
+
+/// the blank-line separated groups of an input, each split into its lines
+fn groups(input: &str) -> Vec<Vec<&str>> {
+    input
+        .split("\n\n")
+        .map(|group| group.lines().collect())
+        .collect()
+}
+
+fn day2_oracle(input: &str) -> (usize, usize) {
+    let mut counts = (0, 0);
+
+    for line in input.lines() {
+        let (policy, password) = line.split_once(": ").unwrap();
+        let (range, letter) = policy.split_once(' ').unwrap();
+        let (low, high) = range.split_once('-').unwrap();
+        let (low, high): (usize, usize) = (low.parse().unwrap(), high.parse().unwrap());
+        let letter = letter.chars().next().unwrap();
+        let password: Vec<char> = password.chars().collect();
+
+        let occurrences = password.iter().filter(|&&c| c == letter).count();
+        if low <= occurrences && occurrences <= high {
+            counts.0 += 1;
+        }
+
+        let at = |position: usize| password.get(position - 1) == Some(&letter);
+        if at(low) != at(high) {
+            counts.1 += 1;
+        }
+    }
+
+    counts
+}
+
+fn day3_oracle(input: &str, (right, down): (usize, usize)) -> usize {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+
+    (0..rows.len())
+        .step_by(down)
+        .enumerate()
+        .filter(|&(step, y)| rows[y][step * right % rows[y].len()] == b'#')
+        .count()
+}
+
+fn day4_oracle(input: &str) -> (usize, usize) {
+    let mut counts = (0, 0);
+
+    for group in groups(input) {
+        let fields: HashMap<&str, &str> = group
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .map(|field| field.split_once(':').unwrap())
+            .collect();
+
+        let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+        if !required.iter().all(|key| fields.contains_key(key)) {
+            continue;
+        }
+        counts.0 += 1;
+
+        let year = |key: &str, low: u32, high: u32| {
+            let value = fields[key];
+            value.len() == 4 && value.parse().is_ok_and(|year| low <= year && year <= high)
+        };
+        let height = |value: &str| match value.len().checked_sub(2).map(|at| value.split_at(at)) {
+            Some((number, "cm")) => number.parse().is_ok_and(|cm| (150..=193).contains(&cm)),
+            Some((number, "in")) => number
+                .parse()
+                .is_ok_and(|inches| (59..=76).contains(&inches)),
+            _ => false,
+        };
+        let hair = |value: &str| {
+            value.len() == 7
+                && value.starts_with('#')
+                && value[1..]
+                    .chars()
+                    .all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+        };
+        let eyes = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+        let pid = |value: &str| value.len() == 9 && value.chars().all(|c| c.is_ascii_digit());
+
+        if year("byr", 1920, 2002)
+            && year("iyr", 2010, 2020)
+            && year("eyr", 2020, 2030)
+            && height(fields["hgt"])
+            && hair(fields["hcl"])
+            && eyes.contains(&fields["ecl"])
+            && pid(fields["pid"])
+        {
+            counts.1 += 1;
+        }
+    }
+
+    counts
+}
+
+fn day6_oracle(input: &str) -> (usize, usize) {
+    // each person's answers as a bitmask of questions a-z
+    let masks = |group: &[&str]| -> Vec<u32> {
+        group
+            .iter()
+            .map(|person| person.bytes().fold(0, |mask, c| mask | 1 << (c - b'a')))
+            .collect()
+    };
+
+    groups(input)
+        .iter()
+        .fold((0, 0), |(anyone, everyone), group| {
+            let masks = masks(group);
+            let any = masks.iter().fold(0, |acc, mask| acc | mask);
+            let all = masks.iter().fold(!0, |acc, mask| acc & mask);
+            (
+                anyone + any.count_ones() as usize,
+                everyone + all.count_ones() as usize,
+            )
+        })
+}
+
+fn day7_oracle(input: &str) -> (usize, usize) {
+    let mut contents: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+
+    for line in input.lines() {
+        let (color, rest) = line.split_once(" bags contain ").unwrap();
+        let inner = rest
+            .trim_end_matches('.')
+            .split(", ")
+            .filter(|bag| *bag != "no other bags")
+            .map(|bag| {
+                let words: Vec<&str> = bag.split(' ').collect();
+                (
+                    words[0].parse().unwrap(),
+                    format!("{} {}", words[1], words[2]),
+                )
+            })
+            .collect();
+        contents.insert(color.to_string(), inner);
+    }
+
+    // walk outwards from shiny gold through every bag that directly holds what we've seen
+    let mut holders = HashSet::new();
+    let mut queue = vec!["shiny gold".to_string()];
+    while let Some(bag) = queue.pop() {
+        for (color, inner) in &contents {
+            if inner.iter().any(|(_, inner)| *inner == bag) && holders.insert(color.clone()) {
+                queue.push(color.clone());
+            }
+        }
+    }
+
+    fn total(contents: &HashMap<String, Vec<(usize, String)>>, bag: &str) -> usize {
+        contents[bag]
+            .iter()
+            .map(|(count, inner)| count * (1 + total(contents, inner)))
+            .sum()
+    }
+
+    (holders.len(), total(&contents, "shiny gold"))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn day1_finds_planted_entries(size in 5..300usize, seed: u64) {
+        let generated = generate(1, size, seed);
+        let values = day1::parse_input(&generated.input).unwrap();
+
+        let part1 = day1::compute_part1(&values).unwrap().map(|answer| answer.to_string());
+        let part2 = day1::compute_part2(&values).unwrap().map(|answer| answer.to_string());
+        prop_assert_eq!(part1, generated.part1);
+        prop_assert_eq!(part2, generated.part2);
+        prop_assert_eq!(day1::count_combinations(&values, 2, day1::TARGET), 1);
+        prop_assert_eq!(day1::count_combinations(&values, 3, day1::TARGET), 1);
+    }
+
+    #[test]
+    fn day2_matches_oracle(size in 1..300usize, seed: u64) {
+        let input = generate(2, size, seed).input;
+        let entries = day2::parse_input(&input).unwrap();
+
+        let answers = (day2::compute_part1(&entries), day2::compute_part2(&entries));
+        prop_assert_eq!(answers, day2_oracle(&input));
+    }
+
+    #[test]
+    fn day3_matches_oracle(size in 1..300usize, seed: u64) {
+        let input = generate(3, size, seed).input;
+        let grid = day3::parse_input(&input).unwrap();
+
+        prop_assert_eq!(day3::compute_part1(&grid), day3_oracle(&input, (3, 1)));
+
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        let product: usize = slopes.iter().map(|&slope| day3_oracle(&input, slope)).product();
+        prop_assert_eq!(day3::compute_part2(&grid), product);
+    }
+
+    #[test]
+    fn day4_matches_oracle(size in 1..300usize, seed: u64) {
+        let input = generate(4, size, seed).input;
+        let passports = day4::parse_input(&input).unwrap();
+
+        let answers = (day4::compute_part1(&passports), day4::compute_part2(&passports));
+        prop_assert_eq!(answers, day4_oracle(&input));
+    }
+
+    #[test]
+    fn day5_finds_planted_seat(size in 3..1100usize, seed: u64) {
+        let generated = generate(5, size, seed);
+        let seats = day5::parse_input(&generated.input).unwrap();
+
+        prop_assert_eq!(day5::compute_part1(&seats).map(|id| id.to_string()), generated.part1);
+        prop_assert_eq!(day5::compute_part2(&seats).map(|id| id.to_string()), generated.part2);
+    }
+
+    #[test]
+    fn day6_matches_oracle(size in 1..300usize, seed: u64) {
+        let input = generate(6, size, seed).input;
+        let groups = day6::parse_input(&input).unwrap();
+
+        let answers = (day6::compute_part1(&groups), day6::compute_part2(&groups));
+        prop_assert_eq!(answers, day6_oracle(&input));
+    }
+
+    #[test]
+    fn day7_matches_oracle(size in 1..150usize, seed: u64) {
+        let input = generate(7, size, seed).input;
+        let rules = day7::parse_input(&input).unwrap();
+
+        let answers = (day7::compute_part1(&rules), day7::compute_part2(&rules));
+        prop_assert_eq!(answers, day7_oracle(&input));
+    }
+
+    #[test]
+    fn day8_finds_planted_corruption(size in 1..300usize, seed: u64) {
+        let generated = generate(8, size, seed);
+        let instructions = day8::parse_input(&generated.input).unwrap();
+
+        let part1 = day8::compute_part1(&instructions).unwrap();
+        prop_assert_eq!(Some(part1.to_string()), generated.part1);
+        prop_assert_eq!(day8::compute_part2(&instructions).map(|acc| acc.to_string()), generated.part2);
+    }
+
+    #[test]
+    fn k_sum_matches_brute_force(
+        // negative entries are refunds
+        values in proptest::collection::vec(-50..50i64, 0..12),
+        k in 0..6usize,
+        target in -150..150i64,
+    ) {
+        let matching = values
+            .iter()
+            .combinations(k)
+            .filter(|combination| combination.iter().copied().sum::<i64>() == target)
+            .count();
+        let exists = matching > 0;
+
+        prop_assert_eq!(day1::count_combinations(&values, k, target), matching);
+        for combination in day1::combinations(&values, k, target) {
+            prop_assert!(combination.indices.windows(2).all(|pair| pair[0] < pair[1]));
+            prop_assert_eq!(combination.entries.iter().sum::<i64>(), target);
+        }
+
+        match day1::find_k_sum(&values, k, target) {
+            Some(found) => {
+                prop_assert_eq!(found.len(), k);
+                prop_assert_eq!(found.iter().sum::<i64>(), target);
+
+                // every entry found has to come from a different entry in the report
+                let mut remaining = values.clone();
+                for value in found {
+                    let index = remaining.iter().position(|&v| v == value);
+                    prop_assert!(index.is_some());
+                    remaining.remove(index.unwrap());
+                }
+            }
+            None => prop_assert!(!exists),
+        }
+    }
+
+    #[test]
+    fn smallest_subset_matches_brute_force(
+        values in proptest::collection::vec(-30..60i64, 0..10),
+        target in -50..150i64,
+    ) {
+        let smallest = (0..=values.len()).find(|&k| day1::count_combinations(&values, k, target) > 0);
+
+        let subset = day1::subset_sum(&values, target, day1::SubsetMode::Smallest);
+        prop_assert_eq!(subset.as_ref().map(|subset| subset.indices.len()), smallest);
+
+        let any = day1::subset_sum(&values, target, day1::SubsetMode::Any);
+        prop_assert_eq!(any.is_some(), smallest.is_some());
+
+        for subset in subset.iter().chain(any.iter()) {
+            prop_assert_eq!(subset.entries.iter().sum::<i64>(), target);
+            prop_assert!(subset.indices.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn entry_round_trips(
+        min in 1..100usize,
+        max in 1..100usize,
+        character in proptest::char::range('a', 'z'),
+        password in "[a-z]{1,30}",
+    ) {
+        let entry = Entry { min, max, character, password };
+        prop_assert_eq!(entry.to_string().parse::<Entry>().unwrap(), entry);
+    }
+
+    #[test]
+    fn seat_round_trips(row in 0..128u8, col in 0..8u8) {
+        let seat = Seat { row, col };
+        prop_assert_eq!(seat.to_string().parse::<Seat>().unwrap(), seat);
+    }
+}